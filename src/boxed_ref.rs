@@ -1,8 +1,10 @@
 use std::fmt;
 use std::marker::PhantomData;
 
+use crate::core::{Mat, MatTraitConst};
 use crate::mod_prelude::OpenCVTypeExternContainer;
 use crate::traits::{Boxed, OpenCVFromExtern, OpenCVIntoExternContainer, OpenCVType};
+use crate::Result;
 
 /// Wrapper for the type implementing [Boxed] trait that allows to retain the lifetime of the referenced object.
 ///
@@ -40,6 +42,16 @@ impl<T: Boxed + Clone> BoxedRef<'_, T> {
 	}
 }
 
+impl BoxedRef<'_, Mat> {
+	/// Copies the referenced `Mat` data into a standalone owned `Mat` that no longer depends on the borrowed object
+	///
+	/// This always allocates a new buffer, even if the referenced `Mat` is continuous.
+	#[inline]
+	pub fn to_owned_mat(&self) -> Result<Mat> {
+		self.reference.try_clone()
+	}
+}
+
 impl<T: OpenCVIntoExternContainer + Boxed> OpenCVIntoExternContainer for BoxedRef<'_, T> {
 	type ExternContainer = T::ExternContainer;
 
@@ -112,6 +124,24 @@ impl<T: Boxed + Clone> BoxedRefMut<'_, T> {
 	}
 }
 
+impl BoxedRefMut<'_, Mat> {
+	/// Copies the referenced `Mat` data into a standalone owned `Mat` that no longer depends on the borrowed object
+	///
+	/// This always allocates a new buffer, even if the referenced `Mat` is continuous.
+	#[inline]
+	pub fn to_owned_mat(&self) -> Result<Mat> {
+		self.reference.try_clone()
+	}
+
+	/// Creates a shorter-lived mutable view of the same `Mat` data without consuming `self`, similar to `&mut *x`
+	///
+	/// `self` can't be accessed while the returned view is alive.
+	#[inline]
+	pub fn reborrow(&mut self) -> Result<BoxedRefMut<Mat>> {
+		Mat::copy_mut(&mut self.reference)
+	}
+}
+
 impl<T: OpenCVIntoExternContainer + Boxed> OpenCVIntoExternContainer for BoxedRefMut<'_, T> {
 	type ExternContainer = T::ExternContainer;
 
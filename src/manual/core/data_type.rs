@@ -1,5 +1,6 @@
 use crate::core;
 use crate::core::{Point3_, Point_, Rect_, Size_, VecN};
+use crate::{Error, Result};
 
 #[inline]
 pub const fn CV_MAT_DEPTH(flags: i32) -> i32 {
@@ -13,6 +14,86 @@ pub const fn CV_MAKETYPE(depth: i32, cn: i32) -> i32 {
 	CV_MAT_DEPTH(depth) + ((cn - 1) << core::CV_CN_SHIFT)
 }
 
+/// Depth of a single channel of a `Mat` element, the Rust counterpart of the `CV_8U`, `CV_8S` and other depth constants
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MatDepth {
+	U8,
+	S8,
+	U16,
+	S16,
+	S32,
+	F32,
+	F64,
+	#[cfg(not(ocvrs_opencv_branch_32))]
+	F16,
+	/// Depth value unknown to this crate, kept for the forward compatibility, see
+	/// [crate::core::MatTraitConstManual::depth_enum()]
+	Other(i32),
+}
+
+impl MatDepth {
+	/// Decodes the depth from the OpenCV depth constant, fails with `StsBadArg` for unknown values
+	///
+	/// This function never returns [MatDepth::Other].
+	pub fn from_opencv_depth(depth: i32) -> Result<Self> {
+		Ok(match depth {
+			core::CV_8U => Self::U8,
+			core::CV_8S => Self::S8,
+			core::CV_16U => Self::U16,
+			core::CV_16S => Self::S16,
+			core::CV_32S => Self::S32,
+			core::CV_32F => Self::F32,
+			core::CV_64F => Self::F64,
+			#[cfg(not(ocvrs_opencv_branch_32))]
+			core::CV_16F => Self::F16,
+			_ => return Err(Error::new(core::StsBadArg, format!("Unknown Mat depth: {depth}"))),
+		})
+	}
+
+	/// OpenCV depth constant for this depth, e.g. `CV_8U`
+	#[inline]
+	pub fn opencv_depth(self) -> i32 {
+		match self {
+			Self::U8 => core::CV_8U,
+			Self::S8 => core::CV_8S,
+			Self::U16 => core::CV_16U,
+			Self::S16 => core::CV_16S,
+			Self::S32 => core::CV_32S,
+			Self::F32 => core::CV_32F,
+			Self::F64 => core::CV_64F,
+			#[cfg(not(ocvrs_opencv_branch_32))]
+			Self::F16 => core::CV_16F,
+			Self::Other(depth) => depth,
+		}
+	}
+}
+
+/// Decoded OpenCV type code of a `Mat` element, see [crate::core::MatTraitConstManual::element_type()]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MatElementType {
+	pub depth: MatDepth,
+	pub channels: i32,
+}
+
+impl MatElementType {
+	/// Decodes the OpenCV type code like `CV_8UC3`, fails with `StsBadArg` for invalid type codes
+	pub fn from_opencv_type(typ: i32) -> Result<Self> {
+		if !(0..core::CV_CN_MAX << core::CV_CN_SHIFT).contains(&typ) {
+			return Err(Error::new(core::StsBadArg, format!("Invalid Mat type code: {typ}")));
+		}
+		Ok(Self {
+			depth: MatDepth::from_opencv_depth(CV_MAT_DEPTH(typ))?,
+			channels: (typ >> core::CV_CN_SHIFT) + 1,
+		})
+	}
+
+	/// OpenCV value for this type as produced by `CV_MAKETYPE()` function
+	#[inline]
+	pub fn opencv_type(self) -> i32 {
+		CV_MAKETYPE(self.depth.opencv_depth(), self.channels)
+	}
+}
+
 /// Implement this trait types that are valid to use as Mat elements.
 ///
 /// # Safety
@@ -62,6 +143,8 @@ data_type!(i32, core::CV_32S, 1);
 // float
 data_type!(f32, core::CV_32F, 1);
 data_type!(f64, core::CV_64F, 1);
+#[cfg(all(feature = "half", not(ocvrs_opencv_branch_32)))]
+data_type!(half::f16, core::CV_16F, 1);
 
 #[cfg(feature = "rgb")]
 data_type!(rgb::RGB8, core::CV_8U, 3);
@@ -1,18 +1,27 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
 use std::convert::TryInto;
 use std::ffi::c_void;
+use std::hash::{Hash, Hasher};
+use std::iter::{self, StepBy};
 use std::marker::PhantomData;
-use std::ops::Deref;
-use std::{fmt, ptr, slice};
+use std::mem::MaybeUninit;
+use std::ops::{Bound, Deref, Range, RangeBounds};
+use std::{array, fmt, ptr, slice};
 
 pub use mat_::*;
+pub use mat_pool::*;
 
 use crate::boxed_ref::{BoxedRef, BoxedRefMut};
 use crate::core::{MatConstIterator, MatExpr, MatSize, Point, Rect, Scalar, Size, UMat};
-use crate::manual::core::DataType;
+use crate::manual::core::{DataType, MatDepth, MatElementType};
 use crate::prelude::*;
-use crate::{core, input_output_array, input_output_array_vector, Error, Result};
+use crate::{core, input_output_array, input_output_array_vector, sys, Error, Result};
 
 mod mat_;
+mod mat_pool;
+#[cfg(feature = "serde")]
+mod mat_serde;
 
 #[inline(always)]
 /// We rely on OpenCV to make sure that the pointer is correctly aligned
@@ -120,6 +129,37 @@ fn match_length(sizes: &[i32], len: usize) -> Result<()> {
 	Ok(())
 }
 
+#[inline]
+fn match_dims_2d(mat: &(impl MatTraitConst + ?Sized)) -> Result<Size> {
+	match *mat.mat_size() {
+		[rows, cols] => Ok(Size::new(cols, rows)),
+		ref mat_size => Err(Error::new(
+			core::StsUnmatchedSizes,
+			format!(
+				"Mat must have 2 dimensions for this operation, but it has: {}",
+				mat_size.len()
+			),
+		)),
+	}
+}
+
+/// Returns the shape and the row stride (in elements) of a 2-dimensional `Mat` suitable for constructing an `ndarray` view
+#[cfg(feature = "ndarray")]
+fn ndarray_shape_row_stride(mat: &(impl MatTraitConst + ?Sized)) -> Result<((usize, usize), usize)> {
+	let size = match_dims_2d(mat)?;
+	// safe because Mat::channels() is always positive
+	let channels = mat.channels() as usize;
+	let step1 = mat.step1(0)?;
+	if step1 % channels != 0 {
+		return Err(Error::new(
+			core::StsUnmatchedSizes,
+			format!("Mat row step: {step1} is not a multiple of the channel count: {channels}"),
+		));
+	}
+	// safe because Mat size can't be negative
+	Ok(((size.height as usize, size.width as usize), step1 / channels))
+}
+
 #[inline(always)]
 fn idx_to_row_col(mat: &(impl MatTraitConst + ?Sized), i0: i32) -> Result<(i32, i32)> {
 	Ok(if mat.is_continuous() {
@@ -137,6 +177,88 @@ fn idx_to_row_col(mat: &(impl MatTraitConst + ?Sized), i0: i32) -> Result<(i32,
 	})
 }
 
+/// Non-failing check for `T` matching the `Mat` type and `row` and `col` being within the bounds of a 2-dimensional `Mat`
+#[inline]
+fn is_valid_2d<T: DataType>(mat: &(impl MatTraitConst + ?Sized), row: i32, col: i32) -> bool {
+	mat.typ() == T::opencv_type() && mat.dims() == 2 && (0..mat.rows()).contains(&row) && (0..mat.cols()).contains(&col)
+}
+
+#[inline]
+fn new_like(mat: &(impl MatTraitConst + ?Sized), s: Scalar) -> Result<Mat> {
+	let mat_size = mat.mat_size();
+	if mat_size.is_empty() {
+		Ok(Mat::default())
+	} else {
+		Mat::new_nd_with_default(&mat_size, mat.typ(), s)
+	}
+}
+
+/// Converts a single channel value of the specified `depth` stored in `bytes` to `f64`, returns `None` for unsupported depths
+#[inline]
+fn channel_to_f64(depth: i32, bytes: &[u8]) -> Option<f64> {
+	Some(match depth {
+		core::CV_8U => f64::from(u8::from_ne_bytes(bytes.try_into().ok()?)),
+		core::CV_8S => f64::from(i8::from_ne_bytes(bytes.try_into().ok()?)),
+		core::CV_16U => f64::from(u16::from_ne_bytes(bytes.try_into().ok()?)),
+		core::CV_16S => f64::from(i16::from_ne_bytes(bytes.try_into().ok()?)),
+		core::CV_32S => f64::from(i32::from_ne_bytes(bytes.try_into().ok()?)),
+		core::CV_32F => f64::from(f32::from_ne_bytes(bytes.try_into().ok()?)),
+		core::CV_64F => f64::from_ne_bytes(bytes.try_into().ok()?),
+		_ => return None,
+	})
+}
+
+/// Returns the size in bytes of a single channel value and of a single element of the `Mat` with the type code `typ`
+fn type_sizes(typ: i32) -> Result<(usize, usize)> {
+	let invalid = || Error::new(core::StsBadArg, format!("Invalid Mat type code: {typ}"));
+	if !(0..core::CV_CN_MAX << core::CV_CN_SHIFT).contains(&typ) {
+		return Err(invalid());
+	}
+	let channel_size = match core::CV_MAT_DEPTH(typ) {
+		core::CV_8U | core::CV_8S => 1,
+		core::CV_16U | core::CV_16S => 2,
+		#[cfg(not(ocvrs_opencv_branch_32))]
+		core::CV_16F => 2,
+		core::CV_32S | core::CV_32F => 4,
+		core::CV_64F => 8,
+		_ => return Err(invalid()),
+	};
+	// safe because the type code is checked to be non-negative above
+	let channels = ((typ >> core::CV_CN_SHIFT) + 1) as usize;
+	Ok((channel_size, channel_size * channels))
+}
+
+#[inline]
+fn unsupported_depth(depth: i32) -> Error {
+	Error::new(
+		core::StsUnsupportedFormat,
+		format!("Mat depth: {depth} is not supported for this operation"),
+	)
+}
+
+/// Converts Rust range bounds into the start and end indices within `0..=len`
+fn match_range_bounds(range: &impl RangeBounds<i32>, len: i32) -> Result<(i32, i32)> {
+	let out_of_range = || Error::new(core::StsOutOfRange, format!("Range is out of bounds 0..{len}"));
+	let start = match range.start_bound() {
+		Bound::Included(&start) => start,
+		Bound::Excluded(&start) => start.checked_add(1).ok_or_else(out_of_range)?,
+		Bound::Unbounded => 0,
+	};
+	let end = match range.end_bound() {
+		Bound::Included(&end) => end.checked_add(1).ok_or_else(out_of_range)?,
+		Bound::Excluded(&end) => end,
+		Bound::Unbounded => len,
+	};
+	if 0 <= start && start <= end && end <= len {
+		Ok((start, end))
+	} else {
+		Err(Error::new(
+			core::StsOutOfRange,
+			format!("Range: {start}..{end} is out of bounds 0..{len}"),
+		))
+	}
+}
+
 #[inline]
 fn row_count_i32(row_count: usize) -> Result<i32> {
 	i32::try_from(row_count).map_err(|_| Error::new(core::StsBadArg, format!("Row count: {row_count} is too high")))
@@ -208,6 +330,305 @@ impl Mat {
 		Ok(out)
 	}
 
+	/// Create a new `Mat` by copying the data from borrowed rows, see [Mat::from_slice_2d()]
+	///
+	/// Every row must have the same length, otherwise an error is returned.
+	#[inline]
+	pub fn from_rows<T: DataType>(rows: &[&[T]]) -> Result<Self> {
+		Self::from_slice_2d(rows)
+	}
+
+	/// Create a new `Mat` with `cols` columns by appending every row yielded by `rows`
+	///
+	/// Every row must contain exactly `cols` elements, otherwise an error is returned. An empty iterator produces a `Mat` with
+	/// zero rows.
+	pub fn collect_rows<T: DataType>(rows: impl Iterator<Item = Vec<T>>, cols: i32) -> Result<Self> {
+		if cols < 0 {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Column count must not be negative, but it's: {cols}"),
+			));
+		}
+		let mut out = Self::new_rows_cols_with_default(0, cols, T::opencv_type(), Scalar::all(0.))?;
+		for (row_n, row) in rows.enumerate() {
+			// safe because `cols` is checked to be non-negative above
+			if row.len() != cols as usize {
+				return Err(Error::new(
+					core::StsUnmatchedSizes,
+					format!(
+						"Unexpected number of items: {} in a row index: {row_n}, expected: {cols}",
+						row.len()
+					),
+				));
+			}
+			if cols > 0 {
+				out.push_back(&Self::new_rows_cols_with_data(1, cols, &row)?)?;
+			}
+		}
+		Ok(out)
+	}
+
+	/// Create a new `Mat` with custom shape by copying the data from a single-dimensional slice
+	///
+	/// Unlike [Mat::new_nd_with_data()] the returned `Mat` owns its data. Empty `sizes` together with empty `data` produce an
+	/// empty `Mat`.
+	pub fn from_slice_nd<T: DataType>(sizes: &[i32], data: &[T]) -> Result<Self> {
+		if sizes.is_empty() && data.is_empty() {
+			return Ok(Self::default());
+		}
+		Self::new_nd_with_data(sizes, data)?.try_clone()
+	}
+
+	/// Create a new `Mat` by copying the data from a 2-dimensional [ndarray::ArrayView2]
+	///
+	/// The elements are copied in the logical order, so arrays with arbitrary strides, including column-major ones, produce
+	/// a row-major `Mat` with the same shape.
+	#[cfg(feature = "ndarray")]
+	pub fn from_ndarray<T: DataType>(arr: ndarray::ArrayView2<T>) -> Result<Self> {
+		let (rows, cols) = arr.dim();
+		let mut out = unsafe { Self::new_rows_cols(row_count_i32(rows)?, col_count_i32(cols)?, T::opencv_type()) }?;
+		if rows > 0 && cols > 0 {
+			let dst = out.data_typed_mut::<T>()?;
+			for (src_row, dst_row) in arr.rows().into_iter().zip(dst.chunks_exact_mut(cols)) {
+				if let Some(src_row) = src_row.as_slice() {
+					dst_row.copy_from_slice(src_row);
+				} else {
+					dst_row.iter_mut().zip(src_row).for_each(|(d, s)| *d = *s);
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Create a new 8-bit 3-channel `Mat` by copying the data from an [image::RgbImage]
+	///
+	/// OpenCV stores color images in the BGR channel order, so the first and the third channels are swapped during the
+	/// conversion. See [MatTraitConstManual::to_image_rgb()] for the reverse operation.
+	#[cfg(feature = "image")]
+	pub fn from_image_rgb(img: &image::RgbImage) -> Result<Self> {
+		let rows = row_count_i32(usize::try_from(img.height())?)?;
+		let cols = col_count_i32(usize::try_from(img.width())?)?;
+		let mut out = Self::new_rows_cols_with_default(rows, cols, core::Vec3b::opencv_type(), Scalar::all(0.))?;
+		for (dst, src) in out.data_typed_mut::<core::Vec3b>()?.iter_mut().zip(img.pixels()) {
+			let [r, g, b] = src.0;
+			*dst = core::Vec3b::from([b, g, r]);
+		}
+		Ok(out)
+	}
+
+	/// Create a new `Mat` that takes the ownership of the `Vec` allocation without copying the data
+	///
+	/// The `Vec` is kept alive by the deallocator installed into the `Mat` and is dropped together with the last `Mat`
+	/// referencing its data.
+	pub fn from_vec<T: DataType + Send>(data: Vec<T>, rows: i32, cols: i32) -> Result<Self> {
+		extern "C" {
+			fn cv_manual_Mat_new_rows_cols_with_external_data(
+				rows: i32,
+				cols: i32,
+				typ: i32,
+				data: *mut c_void,
+				drop: extern "C" fn(*mut c_void),
+				userdata: *mut c_void,
+				ocvrs_return: *mut sys::Result<*mut c_void>,
+			);
+		}
+
+		// the last Mat referencing the data can be dropped on a different thread
+		type Deallocator = Box<dyn FnOnce() + Send>;
+
+		extern "C" fn drop_external_data(userdata: *mut c_void) {
+			// safe because `userdata` is produced by `Box::into_raw()` below and OpenCV calls this function only once
+			let dealloc = unsafe { Box::from_raw(userdata.cast::<Deallocator>()) };
+			dealloc();
+		}
+
+		match_length(&[rows, cols], data.len())?;
+		let mut data = data;
+		let data_ptr = data.as_mut_ptr().cast::<c_void>();
+		let deallocator: Deallocator = Box::new(move || drop(data));
+		let userdata = Box::into_raw(Box::new(deallocator)).cast::<c_void>();
+		return_send!(via ocvrs_return);
+		unsafe {
+			cv_manual_Mat_new_rows_cols_with_external_data(
+				rows,
+				cols,
+				T::opencv_type(),
+				data_ptr,
+				drop_external_data,
+				userdata,
+				ocvrs_return.as_mut_ptr(),
+			)
+		}
+		return_receive!(unsafe ocvrs_return => ret);
+		ret.into_result().map(|ptr| unsafe { Self::from_raw(ptr) }).map_err(|e| {
+			// the ownership is not transferred to the Mat on error
+			drop_external_data(userdata);
+			e
+		})
+	}
+
+	/// Create a new 3-dimensional `Mat` of shape `[planes.len(), rows, cols]` by copying the equally sized 2-dimensional
+	/// `planes` into it
+	///
+	/// All planes must have the same type and size. Empty `planes` produce an empty `Mat`.
+	pub fn stack(planes: &[impl MatTraitConst]) -> Result<Self> {
+		let first = if let Some(first) = planes.first() {
+			first
+		} else {
+			return Ok(Self::default());
+		};
+		let typ = first.typ();
+		let size = match_dims_2d(first)?;
+		for (i, plane) in planes.iter().enumerate() {
+			if plane.typ() != typ {
+				return Err(Error::new(
+					core::StsUnmatchedFormats,
+					format!("Plane at index: {i} has type: {}, but type: {typ} is expected", plane.typ()),
+				));
+			}
+			let plane_size = match_dims_2d(plane)?;
+			if plane_size != size {
+				return Err(Error::new(
+					core::StsUnmatchedSizes,
+					format!("Plane at index: {i} has size: {plane_size:?}, but size: {size:?} is expected"),
+				));
+			}
+		}
+		let mut out = unsafe { Self::new_nd(&[i32::try_from(planes.len())?, size.height, size.width], typ) }?;
+		// safe because Mat size can't be negative
+		let plane_bytes = size.height as usize * size.width as usize * first.elem_size()?;
+		if plane_bytes > 0 {
+			for (plane, dst) in planes.iter().zip(out.data_bytes_mut()?.chunks_exact_mut(plane_bytes)) {
+				let src = plane.strided_bytes()?;
+				for (row_n, dst_row) in dst.chunks_exact_mut(src.row_bytes).enumerate() {
+					// the row is within the plane bounds and contains exactly `row_bytes` bytes
+					dst_row.copy_from_slice(unsafe { slice::from_raw_parts(src.ptr.add(row_n * src.stride), src.row_bytes) });
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Create a new `CV_8UC1` `Mat` where every element is set to the value returned by `f` called with its row and column
+	#[inline]
+	pub fn generate_8uc1(rows: i32, cols: i32, f: impl FnMut(i32, i32) -> u8) -> Result<Self> {
+		Self::generate(rows, cols, f)
+	}
+
+	/// Create a new `CV_32FC1` `Mat` where every element is set to the value returned by `f` called with its row and column
+	#[inline]
+	pub fn generate_32fc1(rows: i32, cols: i32, f: impl FnMut(i32, i32) -> f32) -> Result<Self> {
+		Self::generate(rows, cols, f)
+	}
+
+	fn generate<T: DataType>(rows: i32, cols: i32, f: impl FnMut(i32, i32) -> T) -> Result<Self> {
+		let mut out = unsafe { Self::new_rows_cols(rows, cols, T::opencv_type()) }?;
+		out.fill_from_fn(f)?;
+		Ok(out)
+	}
+
+	/// Create a new multi-channel `Mat` by merging the equally sized 2-dimensional single-channel `channels`, see `core::merge()`
+	///
+	/// All channels must have the same depth and size, any number of channels supported by OpenCV is accepted.
+	pub fn merge_channels(channels: &[impl MatTraitConst]) -> Result<Self> {
+		let first = channels
+			.first()
+			.ok_or_else(|| Error::new(core::StsBadArg, "At least one channel must be passed"))?;
+		let depth = first.depth();
+		let size = match_dims_2d(first)?;
+		let mut planes = core::Vector::<BoxedRef<Mat>>::with_capacity(channels.len());
+		for (i, channel) in channels.iter().enumerate() {
+			if channel.channels() != 1 {
+				return Err(Error::new(
+					core::StsBadArg,
+					format!(
+						"Channel at index: {i} must be single-channel, but it has: {} channels",
+						channel.channels()
+					),
+				));
+			}
+			if channel.depth() != depth {
+				return Err(Error::new(
+					core::StsUnmatchedFormats,
+					format!(
+						"Channel at index: {i} has depth: {}, but depth: {depth} is expected",
+						channel.depth()
+					),
+				));
+			}
+			let channel_size = match_dims_2d(channel)?;
+			if channel_size != size {
+				return Err(Error::new(
+					core::StsUnmatchedSizes,
+					format!("Channel at index: {i} has size: {channel_size:?}, but size: {size:?} is expected"),
+				));
+			}
+			planes.push(channel.row_bounds(0, size.height)?);
+		}
+		let mut out = Self::default();
+		core::merge(&planes, &mut out)?;
+		Ok(out)
+	}
+
+	/// Create a new `Mat` with the specified shape by copying the data from a single-dimensional slice
+	///
+	/// Unlike [Mat::new_rows_cols_with_data()] the returned `Mat` owns its data.
+	#[inline]
+	pub fn copy_rows_cols<T: DataType>(rows: i32, cols: i32, data: &[T]) -> Result<Self> {
+		Self::new_rows_cols_with_data(rows, cols, data)?.try_clone()
+	}
+
+	/// Create a new `Mat` with `channels` channels that references a single-dimensional slice of interleaved channel values
+	///
+	/// `T` must be a single-channel type and the length of `data` must be equal to `rows * cols * channels`.
+	pub fn from_slice_channels<T: DataType>(rows: i32, cols: i32, channels: i32, data: &[T]) -> Result<BoxedRef<Self>> {
+		if T::opencv_channels() != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!(
+					"Element type must be single-channel, but it has: {} channels",
+					T::opencv_channels()
+				),
+			));
+		}
+		if !(1..=core::CV_CN_MAX).contains(&channels) {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Channel count: {channels} must be between 1 and {}", core::CV_CN_MAX),
+			));
+		}
+		match_length(&[rows, cols, channels], data.len())?;
+		let typ = core::CV_MAKETYPE(T::opencv_depth(), channels);
+		let m = unsafe { Self::new_rows_cols_with_data_unsafe_def(rows, cols, typ, data.as_ptr().cast::<c_void>().cast_mut()) }?;
+		Ok(<BoxedRef<Mat>>::from(m))
+	}
+
+	/// Create a new `Mat` that references the bytes of `data` interpreted according to the runtime OpenCV type code `typ`
+	///
+	/// The length of `data` must be equal to `rows * cols` times the element size of `typ` and the data must be aligned to the
+	/// size of a single channel value.
+	pub fn from_bytes_dyn(typ: i32, rows: i32, cols: i32, data: &[u8]) -> Result<BoxedRef<Self>> {
+		let (channel_size, elem_size) = type_sizes(typ)?;
+		if data.len() % elem_size != 0 {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"The length of the data: {} is not a multiple of the element size: {elem_size}",
+					data.len()
+				),
+			));
+		}
+		if data.as_ptr() as usize % channel_size != 0 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("The data is not aligned to the channel size: {channel_size}"),
+			));
+		}
+		match_length(&[rows, cols], data.len() / elem_size)?;
+		let m = unsafe { Self::new_rows_cols_with_data_unsafe_def(rows, cols, typ, data.as_ptr().cast::<c_void>().cast_mut()) }?;
+		Ok(<BoxedRef<Mat>>::from(m))
+	}
+
 	/// Create a new `Mat` that references a single-dimensional slice with custom shape
 	#[inline]
 	pub fn new_rows_cols_with_data<T: DataType>(rows: i32, cols: i32, data: &[T]) -> Result<BoxedRef<Self>> {
@@ -227,6 +648,21 @@ impl Mat {
 		Ok(<BoxedRefMut<Mat>>::from(m))
 	}
 
+	/// Create a new `Mat` that references a mutable slice of uninitialized memory with custom shape, useful as an output buffer
+	///
+	/// # Safety
+	/// Every element of the `Mat` must be written before any element is read as `T`, e.g. through `Mat::data_typed()`.
+	#[inline]
+	pub unsafe fn from_uninit_slice_mut<T: DataType>(
+		rows: i32,
+		cols: i32,
+		data: &mut [MaybeUninit<T>],
+	) -> Result<BoxedRefMut<Self>> {
+		match_length(&[rows, cols], data.len())?;
+		let m = Self::new_rows_cols_with_data_unsafe_def(rows, cols, T::opencv_type(), data.as_mut_ptr().cast::<c_void>())?;
+		Ok(<BoxedRefMut<Mat>>::from(m))
+	}
+
 	/// Create a new `Mat` that references a single-dimensional slice with custom shape
 	#[inline]
 	pub fn new_size_with_data<T: DataType>(size: Size, data: &[T]) -> Result<BoxedRef<Self>> {
@@ -259,6 +695,55 @@ impl Mat {
 		Ok(<BoxedRefMut<Mat>>::from(m))
 	}
 
+	/// Points a `Mat` created from a slice (e.g. with [Mat::new_rows_cols_with_data_mut()]) to the new `data` of the same length
+	///
+	/// The shape and the type of the `Mat` are kept. Returns an error for `Mat`s that own their data or reference data owned by
+	/// another `Mat`, and for submatrices, even those of a `Mat` created from a slice.
+	pub fn rebind_data<'data, T: DataType>(mat: &mut BoxedRefMut<'data, Mat>, data: &'data mut [T]) -> Result<()> {
+		match_format::<T>(mat.typ())?;
+		// Mats that use OpenCV-allocated memory reference it through `u`, it's null only for Mats backed by the user data
+		if !unsafe { sys::cv_Mat_propU(mat.reference.as_raw_mut_Mat()) }.is_null() {
+			return Err(Error::new(
+				core::StsBadArg,
+				"Only Mats that reference the user data can be rebound, but this Mat uses OpenCV-allocated memory",
+			));
+		}
+		if mat.is_submatrix() {
+			return Err(Error::new(core::StsBadArg, "Submatrices can't be rebound"));
+		}
+		match_is_continuous(&mat.reference)?;
+		let mat_size = mat.mat_size().to_vec();
+		*mat = Self::new_nd_with_data_mut(&mat_size, data)?;
+		Ok(())
+	}
+
+	/// Deconstructs a continuous `Mat` into its raw parts transferring the ownership of the data without copying
+	///
+	/// The data is not freed until the `Mat` is reconstructed with [Mat::from_raw_parts()] and dropped.
+	pub fn into_raw_parts(mut self) -> Result<MatRawParts> {
+		match_is_continuous(&self)?;
+		let len = self.total() * self.elem_size()?;
+		Ok(MatRawParts {
+			data: self.data_mut(),
+			len,
+			rows: self.rows(),
+			cols: self.cols(),
+			typ: self.typ(),
+			mat: self.into_raw(),
+		})
+	}
+
+	/// Reconstructs a `Mat` from the raw parts produced by [Mat::into_raw_parts()]
+	///
+	/// # Safety
+	/// `parts` must be produced by [Mat::into_raw_parts()] and can only be used once. The caller is responsible for making sure
+	/// that the memory pointed to by `parts.data` is still the buffer allocated by OpenCV, it must not be freed or reallocated
+	/// by any other allocator in the meantime.
+	#[inline]
+	pub unsafe fn from_raw_parts(parts: MatRawParts) -> Self {
+		Self::from_raw(parts.mat)
+	}
+
 	/// Returns 2 mutable ROIs into a single `Mat` as long as they do not intersect
 	pub fn roi_2_mut<MAT: MatTrait>(m: &mut MAT, roi1: Rect, roi2: Rect) -> Result<(BoxedRefMut<Mat>, BoxedRefMut<Mat>)> {
 		if (roi1 & roi2).empty() {
@@ -271,291 +756,2079 @@ impl Mat {
 			Err(Error::new(core::StsBadArg, "ROIs must not intersect"))
 		}
 	}
-}
 
-pub struct MatIter<'m, T> {
-	iter: Option<MatConstIterator>,
-	_d: PhantomData<&'m T>,
-}
+	/// Returns mutable ROIs into a single `Mat` as long as no two of them intersect
+	///
+	/// Every pair of ROIs is checked for intersection so the cost of the check grows quadratically with the number of ROIs.
+	pub fn roi_n_mut<'m, MAT: MatTrait>(m: &'m mut MAT, rois: &[Rect]) -> Result<Vec<BoxedRefMut<'m, Mat>>> {
+		for (i, &roi1) in rois.iter().enumerate() {
+			if rois[i + 1..].iter().any(|&roi2| !(roi1 & roi2).empty()) {
+				return Err(Error::new(core::StsBadArg, "ROIs must not intersect"));
+			}
+		}
+		let m = m as *mut MAT;
+		rois
+			.iter()
+			.map(|&roi| {
+				// safe because we made sure that the interest areas do not intersect
+				let m = unsafe { m.as_mut().expect("Can't fail") };
+				Mat::roi_mut(m, roi)
+			})
+			.collect()
+	}
 
-impl<'m, T: DataType> MatIter<'m, T> {
-	pub fn new(iter: MatConstIterator) -> Result<Self> {
-		match_format::<T>(iter.typ())?;
-		Ok(Self {
-			iter: Some(iter),
-			_d: PhantomData,
-		})
+	/// Returns 2 mutable non-overlapping row bands of a single `Mat`: rows `0..at` and rows `at..rows`
+	pub fn split_rows_mut<MAT: MatTrait>(m: &mut MAT, at: i32) -> Result<(BoxedRefMut<Mat>, BoxedRefMut<Mat>)> {
+		let rows = m.rows();
+		if (0..=rows).contains(&at) {
+			// safe because the row ranges do not intersect
+			let m2 = unsafe { (m as *mut MAT).as_mut().expect("Can't fail") };
+			let out1 = m.row_bounds_mut(0, at)?;
+			let out2 = m2.row_bounds_mut(at, rows)?;
+			Ok((out1, out2))
+		} else {
+			Err(Error::new(
+				core::StsOutOfRange,
+				format!("Split row: {at} is out of bounds 0..={rows}"),
+			))
+		}
 	}
 }
 
-impl<T: DataType> Iterator for MatIter<'_, T> {
-	type Item = (Point, T);
+impl<T: DataType> TryFrom<&[&[T]]> for Mat {
+	type Error = Error;
 
-	fn next(&mut self) -> Option<Self::Item> {
-		self.iter.as_mut().and_then(|iter| {
-			if iter.has_elements() {
-				// the type is checked by the `MatIter::new()` and we ensure there are still elements by calling `has_elements()`
-				let cur = *unsafe { convert_ptr(iter.ptr()) };
-				let pos = iter.pos().ok()?;
-				iter.seek(1, true).ok()?;
-				Some((pos, cur))
-			} else {
-				None
-			}
-		})
+	/// Same as [Mat::from_slice_2d()]
+	#[inline]
+	fn try_from(s: &[&[T]]) -> Result<Self, Self::Error> {
+		Self::from_slice_2d(s)
 	}
 }
 
-pub struct MatIterMut<'m, T> {
-	iter: Option<MatConstIterator>,
-	_d: PhantomData<&'m mut T>,
+/// Raw parts of a continuous `Mat`, see [Mat::into_raw_parts()]
+#[derive(Debug)]
+pub struct MatRawParts {
+	/// Pointer to the start of the `Mat` data
+	pub data: *mut u8,
+	/// Length of the `Mat` data in bytes
+	pub len: usize,
+	pub rows: i32,
+	pub cols: i32,
+	pub typ: i32,
+	/// OpenCV `Mat` object that holds the ownership of the data
+	mat: *mut c_void,
 }
 
-impl<'m, T: DataType> MatIterMut<'m, T> {
-	pub fn new(iter: MatConstIterator) -> Result<Self> {
-		match_format::<T>(iter.typ())?;
-		Ok(Self {
-			iter: Some(iter),
-			_d: PhantomData,
-		})
-	}
+/// Continuous version of a `Mat` that either shares the data of the source or owns a compact copy of it, see
+/// [MatTraitConstManual::continuous_cow()]
+#[derive(Debug)]
+pub enum MatCow<'m> {
+	/// Shallow copy of the source `Mat` that was already continuous
+	Borrowed(BoxedRef<'m, Mat>),
+	/// Continuous copy of the non-continuous source `Mat`
+	Owned(Mat),
 }
 
-impl<'m, T: DataType> Iterator for MatIterMut<'m, T> {
-	type Item = (Point, &'m mut T);
+impl MatCow<'_> {
+	/// Returns `true` if the data is shared with the source `Mat`
+	#[inline]
+	pub fn is_borrowed(&self) -> bool {
+		matches!(self, Self::Borrowed(_))
+	}
 
-	fn next(&mut self) -> Option<Self::Item> {
-		self.iter.as_mut().and_then(|iter| {
-			if iter.has_elements() {
-				// the type is checked by the `MatIterMut::new()` and we ensure there are still elements by calling `has_elements()`
-				let cur = unsafe { convert_ptr_mut(iter.ptr().cast_mut()) };
-				let pos = iter.pos().ok()?;
-				iter.seek(1, true).ok()?;
-				Some((pos, cur))
-			} else {
-				None
-			}
-		})
+	/// Converts into an owned `Mat`, copying the data if it's shared with the source `Mat`
+	#[inline]
+	pub fn into_owned(self) -> Result<Mat> {
+		match self {
+			Self::Borrowed(mat) => mat.try_clone(),
+			Self::Owned(mat) => Ok(mat),
+		}
 	}
 }
 
-pub(crate) mod mat_forward {
-	use super::*;
+impl Deref for MatCow<'_> {
+	type Target = Mat;
 
 	#[inline]
-	pub fn at<T: DataType>(mat: &(impl MatTraitConst + ?Sized), i0: i32) -> Result<&T> {
-		match_format::<T>(mat.typ())
-			.and_then(|_| match_total(mat, i0))
-			.and_then(|_| unsafe { mat.at_unchecked(i0) })
+	fn deref(&self) -> &Self::Target {
+		match self {
+			Self::Borrowed(mat) => &mat.reference,
+			Self::Owned(mat) => mat,
+		}
 	}
+}
 
-	#[inline]
-	pub fn at_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), i0: i32) -> Result<&mut T> {
-		match_format::<T>(mat.typ()).and_then(|_| match_total(mat, i0))?;
-		unsafe { mat.at_unchecked_mut(i0) }
-	}
+/// Pointer to the data of a 2-dimensional `Mat` together with its stride information, see
+/// [MatTraitConstManual::strided_bytes()]
+#[derive(Clone, Copy, Debug)]
+pub struct StridedBytes<'m> {
+	/// Pointer to the first byte of the first row
+	pub ptr: *const u8,
+	pub rows: usize,
+	/// Number of bytes occupied by the elements of a single row
+	pub row_bytes: usize,
+	/// Distance in bytes between the starts of the consecutive rows
+	pub stride: usize,
+	_d: PhantomData<&'m [u8]>,
+}
 
+/// Builder for a 2-dimensional `Mat`, the element type and both dimensions must be set before calling
+/// [MatBuilder::build()]
+///
+/// Unless [MatBuilder::fill()] is called the elements are initialized with zeros. Unless [MatBuilder::continuous()] is
+/// called with `false` the `Mat` is continuous.
+#[derive(Clone, Copy, Debug)]
+pub struct MatBuilder {
+	rows: Option<i32>,
+	cols: Option<i32>,
+	typ: Option<i32>,
+	fill: Scalar,
+	continuous: bool,
+}
+
+impl Default for MatBuilder {
 	#[inline]
-	pub fn at_2d<T: DataType>(mat: &(impl MatTraitConst + ?Sized), row: i32, col: i32) -> Result<&T> {
-		match_format::<T>(mat.typ())
-			.and_then(|_| match_indices(mat, &[row, col]))
-			.and_then(|_| unsafe { mat.at_2d_unchecked(row, col) })
+	fn default() -> Self {
+		Self {
+			rows: None,
+			cols: None,
+			typ: None,
+			fill: Scalar::default(),
+			continuous: true,
+		}
 	}
+}
 
+impl MatBuilder {
 	#[inline]
-	pub fn at_2d_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), row: i32, col: i32) -> Result<&mut T> {
-		match_format::<T>(mat.typ()).and_then(|_| match_indices(mat, &[row, col]))?;
-		unsafe { mat.at_2d_unchecked_mut(row, col) }
+	pub fn new() -> Self {
+		Self::default()
 	}
 
 	#[inline]
-	pub fn at_pt<T: DataType>(mat: &(impl MatTraitConst + ?Sized), pt: Point) -> Result<&T> {
-		at_2d(mat, pt.y, pt.x)
+	pub fn rows(mut self, rows: i32) -> Self {
+		self.rows = Some(rows);
+		self
 	}
 
 	#[inline]
-	pub fn at_pt_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), pt: Point) -> Result<&mut T> {
-		at_2d_mut(mat, pt.y, pt.x)
+	pub fn cols(mut self, cols: i32) -> Self {
+		self.cols = Some(cols);
+		self
 	}
 
+	/// Sets both dimensions from `size`
 	#[inline]
-	pub fn at_3d<T: DataType>(mat: &(impl MatTraitConst + ?Sized), i0: i32, i1: i32, i2: i32) -> Result<&T> {
-		match_format::<T>(mat.typ())
-			.and_then(|_| match_indices(mat, &[i0, i1, i2]))
-			.and_then(|_| unsafe { mat.at_3d_unchecked(i0, i1, i2) })
+	pub fn size(self, size: Size) -> Self {
+		self.rows(size.height).cols(size.width)
 	}
 
+	/// Sets the OpenCV type of the elements, e.g. `CV_8UC3`
 	#[inline]
-	pub fn at_3d_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), i0: i32, i1: i32, i2: i32) -> Result<&mut T> {
-		match_format::<T>(mat.typ()).and_then(|_| match_indices(mat, &[i0, i1, i2]))?;
-		unsafe { mat.at_3d_unchecked_mut(i0, i1, i2) }
+	pub fn typ(mut self, typ: i32) -> Self {
+		self.typ = Some(typ);
+		self
 	}
 
+	/// Sets the value that every element is initialized with
 	#[inline]
-	pub fn at_nd<'s, T: DataType>(mat: &'s (impl MatTraitConst + ?Sized), idx: &[i32]) -> Result<&'s T> {
-		match_format::<T>(mat.typ())
-			.and_then(|_| match_indices(mat, idx))
-			.and_then(|_| unsafe { mat.at_nd_unchecked(idx) })
+	pub fn fill(mut self, fill: Scalar) -> Self {
+		self.fill = fill;
+		self
 	}
 
+	/// Sets whether the `Mat` must be allocated as a single continuous block like `Mat::new_rows_cols_with_default()` does,
+	/// which is the default
+	///
+	/// With `false` the `Mat` is created as a view into a wider allocation, so a `Mat` with more than one row is not
+	/// continuous. This is useful for testing the code paths that handle submatrices.
 	#[inline]
-	pub fn at_nd_mut<'s, T: DataType>(mat: &'s mut (impl MatTrait + ?Sized), idx: &[i32]) -> Result<&'s mut T> {
-		match_format::<T>(mat.typ()).and_then(|_| match_indices(mat, idx))?;
-		unsafe { mat.at_nd_unchecked_mut(idx) }
+	pub fn continuous(mut self, continuous: bool) -> Self {
+		self.continuous = continuous;
+		self
+	}
+
+	/// Allocates a new `Mat` with the specified parameters
+	pub fn build(&self) -> Result<Mat> {
+		let typ = self.typ.ok_or_else(|| Error::new(core::StsBadArg, "Mat type must be set"))?;
+		let rows = self
+			.rows
+			.ok_or_else(|| Error::new(core::StsBadArg, "Mat row count must be set"))?;
+		let cols = self
+			.cols
+			.ok_or_else(|| Error::new(core::StsBadArg, "Mat column count must be set"))?;
+		type_sizes(typ)?;
+		if rows < 0 || cols < 0 {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Mat dimensions must not be negative, got rows: {rows}, cols: {cols}"),
+			));
+		}
+		if self.continuous {
+			Mat::new_rows_cols_with_default(rows, cols, typ, self.fill)
+		} else {
+			let padded_cols = cols
+				.checked_add(1)
+				.ok_or_else(|| Error::new(core::StsOutOfRange, "Mat column count is too large"))?;
+			let parent = Mat::new_rows_cols_with_default(rows, padded_cols, typ, self.fill)?;
+			// the taken header shares the reference-counted data with `parent`, so it stays valid after `parent` is dropped
+			Ok(Mat::roi(&parent, Rect::new(0, 0, cols, rows))?.reference)
+		}
 	}
 }
 
-pub trait MatTraitConstManual: MatTraitConst {
-	/// Like `Mat::at()` but performs no bounds or type checks
-	///
+pub struct MatIter<'m, T> {
+	iter: Option<MatConstIterator>,
+	back: Option<MatConstIterator>,
+	remaining: usize,
+	_d: PhantomData<&'m T>,
+}
+
+impl<'m, T: DataType> MatIter<'m, T> {
+	pub fn new(iter: MatConstIterator) -> Result<Self> {
+		match_format::<T>(iter.typ())?;
+		let remaining = iter.m().total().saturating_sub(usize::try_from(iter.lpos()?)?);
+		Ok(Self {
+			iter: Some(iter),
+			back: None,
+			remaining,
+			_d: PhantomData,
+		})
+	}
+
+	/// Returns an iterator over the element values only, without their positions
+	#[inline]
+	pub fn values(self) -> iter::Map<Self, fn((Point, T)) -> T> {
+		self.map(|(_, val)| val)
+	}
+
+	/// Returns an iterator over the element positions only, without their values
+	#[inline]
+	pub fn positions(self) -> iter::Map<Self, fn((Point, T)) -> Point> {
+		self.map(|(pos, _)| pos)
+	}
+
+	#[inline]
+	fn empty() -> Self {
+		Self {
+			iter: None,
+			back: None,
+			remaining: 0,
+			_d: PhantomData,
+		}
+	}
+}
+
+impl<T: DataType> Iterator for MatIter<'_, T> {
+	type Item = (Point, T);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.remaining == 0 {
+			return None;
+		}
+		self.iter.as_mut().and_then(|iter| {
+			// the type is checked by the `MatIter::new()` and we ensure there are still elements by checking `remaining`
+			let cur = *unsafe { convert_ptr(iter.ptr()) };
+			let pos = iter.pos().ok()?;
+			iter.seek(1, true).ok()?;
+			self.remaining -= 1;
+			Some((pos, cur))
+		})
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.remaining, Some(self.remaining))
+	}
+}
+
+impl<T: DataType> DoubleEndedIterator for MatIter<'_, T> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.remaining == 0 {
+			return None;
+		}
+		if self.back.is_none() {
+			let mut back = MatConstIterator::copy(self.iter.as_ref()?).ok()?;
+			// safe because `remaining` can't exceed `Mat::total()` which would not fit into memory otherwise
+			back.seek(self.remaining as isize - 1, true).ok()?;
+			self.back = Some(back);
+		}
+		self.back.as_mut().and_then(|back| {
+			// the type is checked by the `MatIter::new()` and `back` always points to the last element that was not yet yielded
+			let cur = *unsafe { convert_ptr(back.ptr()) };
+			let pos = back.pos().ok()?;
+			self.remaining -= 1;
+			// don't move the back iterator beyond the front one
+			if self.remaining > 0 {
+				back.seek(-1, true).ok()?;
+			}
+			Some((pos, cur))
+		})
+	}
+}
+
+impl<T: DataType> ExactSizeIterator for MatIter<'_, T> {}
+
+impl<T> Clone for MatIter<'_, T> {
+	fn clone(&self) -> Self {
+		let copy_iter = |iter: &MatConstIterator| MatConstIterator::copy(iter).expect("Can't copy MatConstIterator");
+		Self {
+			iter: self.iter.as_ref().map(copy_iter),
+			back: self.back.as_ref().map(copy_iter),
+			remaining: self.remaining,
+			_d: PhantomData,
+		}
+	}
+}
+
+/// Iterator over `Mat` elements and their positions in reverse order, see [MatTraitConstManual::iter_rev()]
+pub struct MatIterRev<'m, T> {
+	inner: MatIter<'m, T>,
+}
+
+impl<'m, T: DataType> MatIterRev<'m, T> {
+	pub fn new(iter: MatConstIterator) -> Result<Self> {
+		MatIter::new(iter).map(|inner| Self { inner })
+	}
+}
+
+impl<T: DataType> Iterator for MatIterRev<'_, T> {
+	type Item = (Point, T);
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		self.inner.next_back()
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<T: DataType> DoubleEndedIterator for MatIterRev<'_, T> {
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.inner.next()
+	}
+}
+
+impl<T: DataType> ExactSizeIterator for MatIterRev<'_, T> {}
+
+/// Iterator over the rows of a 2-dimensional `Mat`, see [MatTraitConstManual::rows_typed()]
+pub struct MatRowsIter<'m, T> {
+	data: *const u8,
+	step: usize,
+	width: usize,
+	rows: Range<usize>,
+	_d: PhantomData<&'m T>,
+}
+
+impl<'m, T: DataType> MatRowsIter<'m, T> {
 	/// # Safety
-	/// Caller must ensure that index is within Mat bounds
+	/// Caller must ensure that the `Mat` has 2 dimensions and every row contains exactly `width` values of the type `T`
+	unsafe fn new(mat: &'m (impl MatTraitConst + ?Sized), width: usize) -> Self {
+		let data = mat.data();
+		// safe because Mat::rows() can't be negative for a 2-dimensional Mat
+		let rows = if data.is_null() {
+			0
+		} else {
+			mat.rows() as usize
+		};
+		Self {
+			data,
+			step: mat.mat_step().get(0),
+			width,
+			rows: 0..rows,
+			_d: PhantomData,
+		}
+	}
+}
+
+impl<'m, T: DataType> Iterator for MatRowsIter<'m, T> {
+	type Item = &'m [T];
+
 	#[inline]
-	unsafe fn at_unchecked<T: DataType>(&self, i0: i32) -> Result<&T> {
-		let mat_size = self.size()?;
-		let (i, j) = if self.is_continuous() || mat_size.width == 1 {
-			(0, i0)
-		} else if mat_size.height == 1 {
-			(i0, 0)
+	fn next(&mut self) -> Option<Self::Item> {
+		self.rows.next().map(|row| {
+			// the type is checked by `rows_typed()` and `row` is within the Mat bounds
+			unsafe { slice::from_raw_parts(self.data.add(row * self.step).cast::<T>(), self.width) }
+		})
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.rows.size_hint()
+	}
+}
+
+impl<T: DataType> ExactSizeIterator for MatRowsIter<'_, T> {}
+
+/// Iterator over the values of a single channel of a `Mat`, see [MatTraitConstManual::channel_view()]
+pub struct MatChannels<'m, T> {
+	rows: MatRowsIter<'m, T>,
+	channels: usize,
+	ch: usize,
+	row: Option<StepBy<slice::Iter<'m, T>>>,
+}
+
+impl<'m, T: DataType> Iterator for MatChannels<'m, T> {
+	type Item = &'m T;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(out) = self.row.as_mut().and_then(Iterator::next) {
+				return Some(out);
+			}
+			let row = self.rows.next()?;
+			self.row = Some(row.get(self.ch..).unwrap_or(&[]).iter().step_by(self.channels));
+		}
+	}
+}
+
+/// Iterator over all windows of the specified size that fit into a 2-dimensional `Mat`, see [MatTraitConstManual::windows()]
+pub struct MatWindows<'m, M> {
+	mat: &'m M,
+	size: Size,
+	max: Point,
+	pos: Point,
+}
+
+impl<'m, M: MatTraitConst> Iterator for MatWindows<'m, M> {
+	type Item = (Point, BoxedRef<'m, Mat>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.pos.y > self.max.y {
+			return None;
+		}
+		let pos = self.pos;
+		let roi = Mat::roi(self.mat, Rect::from_point_size(pos, self.size)).ok()?;
+		if pos.x < self.max.x {
+			self.pos.x += 1;
 		} else {
-			let i = i0 / mat_size.height;
-			(i, i0 - i * mat_size.height)
+			self.pos = Point::new(0, pos.y + 1);
+		}
+		Some((pos, roi))
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		// safe because `pos` never goes beyond `max` by more than one row and the `max` coordinates are non-negative
+		let remaining = if self.pos.y > self.max.y {
+			0
+		} else {
+			let width = self.max.x as usize + 1;
+			(self.max.y - self.pos.y) as usize * width + (width - self.pos.x as usize)
 		};
-		self.ptr_2d(i, j).map(|ptr| convert_ptr(ptr))
+		(remaining, Some(remaining))
 	}
+}
+
+impl<M: MatTraitConst> ExactSizeIterator for MatWindows<'_, M> {}
+
+/// Iterator over the borrowed row or column views of a 2-dimensional `Mat`, see [MatTraitConstManual::row_views()] and
+/// [MatTraitConstManual::col_views()]
+pub struct MatViews<'m, M: ?Sized> {
+	mat: &'m M,
+	view: fn(&'m M, i32) -> Result<BoxedRef<'m, Mat>>,
+	range: Range<i32>,
+}
+
+impl<'m, M: MatTraitConst + ?Sized> MatViews<'m, M> {
+	fn new(mat: &'m M, view: fn(&'m M, i32) -> Result<BoxedRef<'m, Mat>>, count: impl FnOnce(Size) -> i32) -> Result<Self> {
+		let count = if mat.dims() == 0 {
+			0
+		} else {
+			count(match_dims_2d(mat)?)
+		};
+		Ok(Self {
+			mat,
+			view,
+			range: 0..count,
+		})
+	}
+}
+
+impl<'m, M: MatTraitConst + ?Sized> Iterator for MatViews<'m, M> {
+	type Item = BoxedRef<'m, Mat>;
 
-	/// Like `Mat::at_2d()` but performs no bounds or type checks
-	/// # Safety
-	/// Caller must ensure that indices are within Mat bounds
 	#[inline]
-	unsafe fn at_2d_unchecked<T: DataType>(&self, row: i32, col: i32) -> Result<&T> {
-		self.ptr_2d(row, col).map(|ptr| convert_ptr(ptr))
+	fn next(&mut self) -> Option<Self::Item> {
+		self.range.next().and_then(|i| (self.view)(self.mat, i).ok())
 	}
 
-	/// Like `Mat::at_pt()` but performs no bounds or type checks
-	/// # Safety
-	/// Caller must ensure that point is within Mat bounds
 	#[inline]
-	unsafe fn at_pt_unchecked<T: DataType>(&self, pt: Point) -> Result<&T> {
-		self.at_2d_unchecked(pt.y, pt.x)
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.range.size_hint()
+	}
+}
+
+impl<M: MatTraitConst + ?Sized> ExactSizeIterator for MatViews<'_, M> {}
+
+pub struct MatIterMut<'m, T> {
+	iter: Option<MatConstIterator>,
+	_d: PhantomData<&'m mut T>,
+}
+
+impl<'m, T: DataType> MatIterMut<'m, T> {
+	pub fn new(iter: MatConstIterator) -> Result<Self> {
+		match_format::<T>(iter.typ())?;
+		Ok(Self {
+			iter: Some(iter),
+			_d: PhantomData,
+		})
+	}
+}
+
+impl<'m, T: DataType> Iterator for MatIterMut<'m, T> {
+	type Item = (Point, &'m mut T);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.as_mut().and_then(|iter| {
+			if iter.has_elements() {
+				// the type is checked by the `MatIterMut::new()` and we ensure there are still elements by calling `has_elements()`
+				let cur = unsafe { convert_ptr_mut(iter.ptr().cast_mut()) };
+				let pos = iter.pos().ok()?;
+				iter.seek(1, true).ok()?;
+				Some((pos, cur))
+			} else {
+				None
+			}
+		})
+	}
+}
+
+/// Iterator over references to `Mat` elements and their positions, used by the `IntoIterator` implementation for `&Mat_`
+pub struct MatIterRef<'m, T> {
+	iter: Option<MatConstIterator>,
+	_d: PhantomData<&'m T>,
+}
+
+impl<'m, T: DataType> MatIterRef<'m, T> {
+	pub fn new(iter: MatConstIterator) -> Result<Self> {
+		match_format::<T>(iter.typ())?;
+		Ok(Self {
+			iter: Some(iter),
+			_d: PhantomData,
+		})
+	}
+}
+
+impl<'m, T: DataType> Iterator for MatIterRef<'m, T> {
+	type Item = (Point, &'m T);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.iter.as_mut().and_then(|iter| {
+			if iter.has_elements() {
+				// the type is checked by the `MatIterRef::new()` and we ensure there are still elements by calling `has_elements()`
+				let cur = unsafe { convert_ptr(iter.ptr()) };
+				let pos = iter.pos().ok()?;
+				iter.seek(1, true).ok()?;
+				Some((pos, cur))
+			} else {
+				None
+			}
+		})
+	}
+}
+
+/// Compares 2 `Mat`s of the type `T` elementwise with the absolute tolerance `eps`
+///
+/// Every channel of every element is converted to `f64` before comparison. `Mat`s that can't be compared elementwise are
+/// reported as errors rather than as unequal: `StsUnmatchedFormats` if either of them is not of the type `T` and
+/// `StsUnmatchedSizes` if their shapes differ.
+pub fn mat_approx_eq<T: DataType>(a: &impl MatTraitConst, b: &impl MatTraitConst, eps: f64) -> Result<bool> {
+	match_format::<T>(a.typ()).and_then(|_| match_format::<T>(b.typ()))?;
+	let (a_size, b_size) = (a.mat_size(), b.mat_size());
+	if *a_size != *b_size {
+		return Err(Error::new(
+			core::StsUnmatchedSizes,
+			format!("Mat shapes don't match: {:?} and {:?}", &*a_size, &*b_size),
+		));
+	}
+	let (a_cont, b_cont);
+	let a_bytes = if a.is_continuous() {
+		a.data_bytes()?
+	} else {
+		a_cont = a.try_clone()?;
+		a_cont.data_bytes()?
+	};
+	let b_bytes = if b.is_continuous() {
+		b.data_bytes()?
+	} else {
+		b_cont = b.try_clone()?;
+		b_cont.data_bytes()?
+	};
+	let depth = a.depth();
+	let channel_size = a.elem_size1();
+	for (a_ch, b_ch) in a_bytes.chunks_exact(channel_size).zip(b_bytes.chunks_exact(channel_size)) {
+		let a_val = channel_to_f64(depth, a_ch).ok_or_else(|| unsupported_depth(depth))?;
+		let b_val = channel_to_f64(depth, b_ch).ok_or_else(|| unsupported_depth(depth))?;
+		let diff = (a_val - b_val).abs();
+		if diff.is_nan() || diff > eps {
+			return Ok(false);
+		}
+	}
+	Ok(true)
+}
+
+/// Stacks 2-dimensional `Mat`s of the same type and column count on top of each other into a new `Mat`
+///
+/// Empty `mats` produce an empty `Mat`.
+#[inline]
+pub fn vconcat_mats(mats: &[impl MatTraitConst]) -> Result<Mat> {
+	concat_mats(mats, true)
+}
+
+/// Places 2-dimensional `Mat`s of the same type and row count side by side into a new `Mat`
+///
+/// Empty `mats` produce an empty `Mat`.
+#[inline]
+pub fn hconcat_mats(mats: &[impl MatTraitConst]) -> Result<Mat> {
+	concat_mats(mats, false)
+}
+
+fn concat_mats(mats: &[impl MatTraitConst], vertical: bool) -> Result<Mat> {
+	let first = if let Some(first) = mats.first() {
+		first
+	} else {
+		return Ok(Mat::default());
+	};
+	let typ = first.typ();
+	let first_size = match_dims_2d(first)?;
+	let mut out_size = if vertical {
+		Size::new(first_size.width, 0)
+	} else {
+		Size::new(0, first_size.height)
+	};
+	for (i, mat) in mats.iter().enumerate() {
+		if mat.typ() != typ {
+			return Err(Error::new(
+				core::StsUnmatchedFormats,
+				format!("Mat at index: {i} has type: {}, but type: {typ} is expected", mat.typ()),
+			));
+		}
+		let size = match_dims_2d(mat)?;
+		let (band, shared, expected) = if vertical {
+			(size.height, size.width, first_size.width)
+		} else {
+			(size.width, size.height, first_size.height)
+		};
+		if shared != expected {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"Mat at index: {i} has {shared} {}, but {expected} is expected",
+					if vertical {
+						"columns"
+					} else {
+						"rows"
+					}
+				),
+			));
+		}
+		let out_band = if vertical {
+			&mut out_size.height
+		} else {
+			&mut out_size.width
+		};
+		*out_band = out_band
+			.checked_add(band)
+			.ok_or_else(|| Error::new(core::StsOutOfRange, "Resulting Mat size is too large"))?;
+	}
+	let mut out = unsafe { Mat::new_size(out_size, typ) }?;
+	let mut offset = 0;
+	for mat in mats {
+		let size = mat.size()?;
+		let roi = if vertical {
+			Rect::new(0, offset, size.width, size.height)
+		} else {
+			Rect::new(offset, 0, size.width, size.height)
+		};
+		if !roi.empty() {
+			mat.copy_to(&mut Mat::roi_mut(&mut out, roi)?)?;
+		}
+		offset += if vertical {
+			size.height
+		} else {
+			size.width
+		};
+	}
+	Ok(out)
+}
+
+pub(crate) mod mat_forward {
+	use super::*;
+
+	#[inline]
+	pub fn at<T: DataType>(mat: &(impl MatTraitConst + ?Sized), i0: i32) -> Result<&T> {
+		match_format::<T>(mat.typ())
+			.and_then(|_| match_total(mat, i0))
+			.and_then(|_| unsafe { mat.at_unchecked(i0) })
+	}
+
+	#[inline]
+	pub fn at_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), i0: i32) -> Result<&mut T> {
+		match_format::<T>(mat.typ()).and_then(|_| match_total(mat, i0))?;
+		unsafe { mat.at_unchecked_mut(i0) }
+	}
+
+	#[inline]
+	pub fn at_2d<T: DataType>(mat: &(impl MatTraitConst + ?Sized), row: i32, col: i32) -> Result<&T> {
+		match_format::<T>(mat.typ())
+			.and_then(|_| match_indices(mat, &[row, col]))
+			.and_then(|_| unsafe { mat.at_2d_unchecked(row, col) })
+	}
+
+	#[inline]
+	pub fn at_2d_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), row: i32, col: i32) -> Result<&mut T> {
+		match_format::<T>(mat.typ()).and_then(|_| match_indices(mat, &[row, col]))?;
+		unsafe { mat.at_2d_unchecked_mut(row, col) }
+	}
+
+	#[inline]
+	pub fn at_pt<T: DataType>(mat: &(impl MatTraitConst + ?Sized), pt: Point) -> Result<&T> {
+		at_2d(mat, pt.y, pt.x)
+	}
+
+	#[inline]
+	pub fn at_pt_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), pt: Point) -> Result<&mut T> {
+		at_2d_mut(mat, pt.y, pt.x)
+	}
+
+	#[inline]
+	pub fn at_3d<T: DataType>(mat: &(impl MatTraitConst + ?Sized), i0: i32, i1: i32, i2: i32) -> Result<&T> {
+		match_format::<T>(mat.typ())
+			.and_then(|_| match_indices(mat, &[i0, i1, i2]))
+			.and_then(|_| unsafe { mat.at_3d_unchecked(i0, i1, i2) })
+	}
+
+	#[inline]
+	pub fn at_3d_mut<T: DataType>(mat: &mut (impl MatTrait + ?Sized), i0: i32, i1: i32, i2: i32) -> Result<&mut T> {
+		match_format::<T>(mat.typ()).and_then(|_| match_indices(mat, &[i0, i1, i2]))?;
+		unsafe { mat.at_3d_unchecked_mut(i0, i1, i2) }
+	}
+
+	#[inline]
+	pub fn at_nd<'s, T: DataType>(mat: &'s (impl MatTraitConst + ?Sized), idx: &[i32]) -> Result<&'s T> {
+		match_format::<T>(mat.typ())
+			.and_then(|_| match_indices(mat, idx))
+			.and_then(|_| unsafe { mat.at_nd_unchecked(idx) })
+	}
+
+	#[inline]
+	pub fn at_nd_mut<'s, T: DataType>(mat: &'s mut (impl MatTrait + ?Sized), idx: &[i32]) -> Result<&'s mut T> {
+		match_format::<T>(mat.typ()).and_then(|_| match_indices(mat, idx))?;
+		unsafe { mat.at_nd_unchecked_mut(idx) }
+	}
+}
+
+pub trait MatTraitConstManual: MatTraitConst {
+	/// Like `Mat::at()` but performs no bounds or type checks
+	///
+	/// # Safety
+	/// Caller must ensure that index is within Mat bounds
+	#[inline]
+	unsafe fn at_unchecked<T: DataType>(&self, i0: i32) -> Result<&T> {
+		let mat_size = self.size()?;
+		let (i, j) = if self.is_continuous() || mat_size.width == 1 {
+			(0, i0)
+		} else if mat_size.height == 1 {
+			(i0, 0)
+		} else {
+			let i = i0 / mat_size.height;
+			(i, i0 - i * mat_size.height)
+		};
+		self.ptr_2d(i, j).map(|ptr| convert_ptr(ptr))
+	}
+
+	/// Like `Mat::at_2d()` but performs no bounds or type checks
+	/// # Safety
+	/// Caller must ensure that indices are within Mat bounds
+	#[inline]
+	unsafe fn at_2d_unchecked<T: DataType>(&self, row: i32, col: i32) -> Result<&T> {
+		self.ptr_2d(row, col).map(|ptr| convert_ptr(ptr))
+	}
+
+	/// Like `Mat::at_2d()`, but returns `None` instead of constructing an `Error` when `T` doesn't match the `Mat` type or the
+	/// indices are out of bounds
+	#[inline]
+	fn get_2d<T: DataType>(&self, row: i32, col: i32) -> Option<&T> {
+		if is_valid_2d::<T>(self, row, col) {
+			unsafe { self.at_2d_unchecked(row, col) }.ok()
+		} else {
+			None
+		}
+	}
+
+	/// Returns a raw pointer to the element at the specified `row` and `col` after checking the type and the bounds
+	///
+	/// The pointer is not dereferenced and is valid only as long as the `Mat` data is alive and not reallocated.
+	#[inline]
+	fn elem_ptr<T: DataType>(&self, row: i32, col: i32) -> Result<*const T> {
+		match_format::<T>(self.typ()).and_then(|_| match_indices(self, &[row, col]))?;
+		self.ptr_2d(row, col).map(|ptr| ptr.cast::<T>())
+	}
+
+	/// N-dimensional version of [MatTraitConstManual::elem_ptr()]
+	#[inline]
+	fn elem_ptr_nd<T: DataType>(&self, idx: &[i32]) -> Result<*const T> {
+		match_format::<T>(self.typ()).and_then(|_| match_indices(self, idx))?;
+		self.ptr_nd(idx).map(|ptr| ptr.cast::<T>())
+	}
+
+	/// Like `Mat::at_pt()` but performs no bounds or type checks
+	/// # Safety
+	/// Caller must ensure that point is within Mat bounds
+	#[inline]
+	unsafe fn at_pt_unchecked<T: DataType>(&self, pt: Point) -> Result<&T> {
+		self.at_2d_unchecked(pt.y, pt.x)
+	}
+
+	/// Like `Mat::at_3d()` but performs no bounds or type checks
+	/// # Safety
+	/// Caller must ensure that indices are within Mat bounds
+	#[inline]
+	unsafe fn at_3d_unchecked<T: DataType>(&self, i0: i32, i1: i32, i2: i32) -> Result<&T> {
+		self.ptr_3d(i0, i1, i2).map(|ptr| convert_ptr(ptr))
+	}
+
+	/// Like `Mat::at_nd()` but performs no bounds or type checks
+	/// # Safety
+	/// Caller must ensure that indices are within Mat bounds
+	#[inline]
+	unsafe fn at_nd_unchecked<T: DataType>(&self, idx: &[i32]) -> Result<&T> {
+		self.ptr_nd(idx).map(|ptr| convert_ptr(ptr))
+	}
+
+	/// Return a complete read-only row
+	#[inline]
+	fn at_row<T: DataType>(&self, row: i32) -> Result<&[T]> {
+		match_format::<T>(self.typ())
+			.and_then(|_| match_indices(self, &[row, 0]))
+			.and_then(|_| unsafe { self.at_row_unchecked(row) })
+	}
+
+	/// Like `Mat::at_row()` but performs no bounds or type checks
+	/// # Safety
+	/// Caller must ensure that index is within Mat bounds
+	#[inline]
+	unsafe fn at_row_unchecked<T: DataType>(&self, row: i32) -> Result<&[T]> {
+		// safe because Mat::size() can't be negative
+		let width = self.size()?.width as usize;
+		self.ptr(row).map(|row| {
+			if row.is_null() {
+				&[]
+			} else {
+				slice::from_raw_parts(convert_ptr(row), width)
+			}
+		})
+	}
+
+	/// Return complete read-only rows `start..end` as a single slice
+	///
+	/// The rows must be stored continuously in memory, e.g. a block of rows of a column ROI is not, in this case an error is
+	/// returned. An empty range, including `rows..rows`, or a `Mat` without columns produces an empty slice.
+	fn at_rows<T: DataType>(&self, start: i32, end: i32) -> Result<&[T]> {
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		let (rows, cols) = (size.height, size.width);
+		if !(0..=rows).contains(&start) || !(start..=rows).contains(&end) {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Row range: {start}..{end} out of bounds 0..={rows}"),
+			));
+		}
+		if start == end || cols == 0 {
+			return Ok(&[]);
+		}
+		if end - start > 1 {
+			match_is_continuous(self)?;
+		}
+		// safe because Mat::cols() can't be negative for a 2-dimensional Mat and `start <= end`
+		let len = (end - start) as usize * cols as usize;
+		self.ptr(start).map(|row| {
+			if row.is_null() {
+				&[][..]
+			} else {
+				// the type is checked above and the rows are within the Mat bounds and continuous
+				unsafe { slice::from_raw_parts(convert_ptr(row), len) }
+			}
+		})
+	}
+
+	#[inline]
+	fn is_allocated(&self) -> bool {
+		!self.data().is_null()
+	}
+
+	/// Returns the current reference count of the underlying OpenCV data buffer
+	///
+	/// Returns `None` if the `Mat` doesn't own reference counted memory, e.g. for the `Mat`s created with the `_with_data`
+	/// constructors. This is intended only for debugging and doesn't affect the reference count.
+	#[inline]
+	fn ref_count(&self) -> Option<i32> {
+		extern "C" {
+			fn cv_manual_Mat_refcount(instance: *const c_void) -> i32;
+		}
+		let ref_count = unsafe { cv_manual_Mat_refcount(self.as_raw_Mat()) };
+		if ref_count >= 0 {
+			Some(ref_count)
+		} else {
+			None
+		}
+	}
+
+	/// Returns the element of a 2-dimensional `Mat` at the specified position, out of bounds coordinates are mapped back inside
+	/// of the `Mat` according to `border`, see `core::border_interpolate()`
+	///
+	/// `BORDER_CONSTANT` produces an error for out of bounds coordinates because there is no element to return.
+	fn sample_border<T: DataType>(&self, row: i32, col: i32, border: core::BorderTypes) -> Result<T> {
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		if size.width == 0 || size.height == 0 {
+			return Err(Error::new(core::StsBadArg, "Mat must not be empty for this operation"));
+		}
+		let interpolate = |p: i32, len: i32| {
+			if (0..len).contains(&p) {
+				Ok(p)
+			} else {
+				core::border_interpolate(p, len, border as i32)
+			}
+		};
+		let (mapped_row, mapped_col) = (interpolate(row, size.height)?, interpolate(col, size.width)?);
+		if mapped_row < 0 || mapped_col < 0 {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Position row: {row}, col: {col} is outside of the Mat and border: {border:?} doesn't map it inside"),
+			));
+		}
+		// safe because the type is checked above and border_interpolate() returns indices within the Mat bounds
+		Ok(*unsafe { self.at_2d_unchecked::<T>(mapped_row, mapped_col) }?)
+	}
+
+	/// Returns the decoded depth of the `Mat` elements, a more convenient alternative to `Mat::depth()` for matching
+	///
+	/// Depths unknown to this crate are returned as [MatDepth::Other], combine with `Mat::channels()` using
+	/// [MatElementType] to get the full type.
+	#[inline]
+	fn depth_enum(&self) -> MatDepth {
+		let depth = self.depth();
+		MatDepth::from_opencv_depth(depth).unwrap_or(MatDepth::Other(depth))
+	}
+
+	/// Returns the decoded type of the `Mat` elements, a more convenient alternative to `Mat::typ()` for matching
+	#[inline]
+	fn element_type(&self) -> MatElementType {
+		MatElementType {
+			depth: self.depth_enum(),
+			channels: self.channels(),
+		}
+	}
+
+	/// Checks whether both `Mat`s reference the same underlying data buffer, e.g. after a shallow copy or taking a submatrix
+	///
+	/// Empty `Mat`s without data never share a buffer.
+	#[inline]
+	fn is_same_buffer(&self, other: &impl MatTraitConst) -> bool {
+		let datastart = self.datastart();
+		!datastart.is_null() && datastart == other.datastart()
+	}
+
+	/// Returns underlying data array as byte slice, `Mat` must be continuous
+	#[inline]
+	fn data_bytes(&self) -> Result<&[u8]> {
+		match_is_continuous(self).map(|_| unsafe { self.data_bytes_unchecked() })
+	}
+
+	/// Like `Mat::data_bytes()`, but skips the continuity check
+	///
+	/// # Safety
+	/// Caller must ensure that `Mat` is continuous and its data is allocated
+	#[inline]
+	unsafe fn data_bytes_unchecked(&self) -> &[u8] {
+		let data = self.data();
+		if data.is_null() {
+			&[]
+		} else {
+			slice::from_raw_parts(data, self.total() * self.elem_size1() * self.channels() as usize)
+		}
+	}
+
+	/// Returns the data pointer of a 2-dimensional `Mat` together with the row size and stride in bytes
+	///
+	/// Unlike `Mat::data_bytes()` this also works for non-continuous `Mat`s like submatrices.
+	#[inline]
+	fn strided_bytes(&self) -> Result<StridedBytes> {
+		let size = match_dims_2d(self)?;
+		// safe because Mat size can't be negative
+		Ok(StridedBytes {
+			ptr: self.data(),
+			rows: size.height as usize,
+			row_bytes: size.width as usize * self.elem_size()?,
+			stride: self.step1(0)? * self.elem_size1(),
+			_d: PhantomData,
+		})
+	}
+
+	/// Returns the distance in bytes between the consecutive indices of each dimension, works for any number of dimensions
+	///
+	/// For a 2-dimensional `Mat` this is `[row_stride, elem_size]`.
+	#[inline]
+	fn strides_bytes(&self) -> Vec<usize> {
+		let mat_step = self.mat_step();
+		(0..self.dims()).map(|i| mat_step.get(i)).collect()
+	}
+
+	/// Like [MatTraitConstManual::strides_bytes()], but the distances are measured in elements instead of bytes
+	#[inline]
+	fn strides_elems(&self) -> Vec<usize> {
+		let elem_size = self.elem_size1() * self.channels() as usize;
+		self.strides_bytes().into_iter().map(|stride| stride / elem_size).collect()
+	}
+
+	/// Returns a new `Mat` extended by the specified number of pixels on each side, see `core::copy_make_border()`
+	///
+	/// `value` is only used to fill the border when `border` is `BORDER_CONSTANT`. Paddings must not be negative.
+	fn pad(&self, top: i32, bottom: i32, left: i32, right: i32, border: core::BorderTypes, value: Scalar) -> Result<Mat>
+	where
+		Self: Sized,
+	{
+		if top < 0 || bottom < 0 || left < 0 || right < 0 {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Paddings must not be negative, got top: {top}, bottom: {bottom}, left: {left}, right: {right}"),
+			));
+		}
+		let src = core::_InputArray::from_mat(self)?;
+		let mut out = Mat::default();
+		core::copy_make_border(&src, &mut out, top, bottom, left, right, border as i32, value)?;
+		Ok(out)
+	}
+
+	/// Places this and the `other` 2-dimensional `Mat` side by side into a new `Mat`, see [hconcat_mats()]
+	///
+	/// Both `Mat`s must have the same type and row count.
+	#[inline]
+	fn hstack(&self, other: &impl MatTraitConst) -> Result<Mat> {
+		hconcat_mats(&[self.row_bounds(0, self.rows())?, other.row_bounds(0, other.rows())?])
+	}
+
+	/// Stacks the `other` 2-dimensional `Mat` below this one into a new `Mat`, see [vconcat_mats()]
+	///
+	/// Both `Mat`s must have the same type and column count.
+	#[inline]
+	fn vstack(&self, other: &impl MatTraitConst) -> Result<Mat> {
+		vconcat_mats(&[self.row_bounds(0, self.rows())?, other.row_bounds(0, other.rows())?])
+	}
+
+	/// Writes the transposition of a 2-dimensional `Mat` into `dst`, see `core::transpose()`
+	///
+	/// `dst` is reallocated only if its shape or type doesn't match the transposed `Mat`, otherwise its memory is reused.
+	fn transpose_into(&self, dst: &mut impl MatTrait) -> Result<()>
+	where
+		Self: Sized,
+	{
+		match_dims_2d(self)?;
+		let src = core::_InputArray::from_mat(self)?;
+		let mut dst = core::_OutputArray::from_mat_mut(dst)?;
+		core::transpose(&src, &mut dst)
+	}
+
+	/// Resizes a 2-dimensional `Mat` into `dst`, the current size of `dst` determines the output dimensions, see
+	/// `imgproc::resize()`
+	///
+	/// `dst` must not be empty. Its memory is reused if its type matches the type of the `Mat`, otherwise it's reallocated with
+	/// the same size.
+	#[cfg(ocvrs_has_module_imgproc)]
+	fn resize_into(&self, dst: &mut impl MatTrait, interp: crate::imgproc::InterpolationFlags) -> Result<()>
+	where
+		Self: Sized,
+	{
+		if dst.empty() {
+			return Err(Error::new(
+				core::StsBadArg,
+				"Destination Mat must not be empty, its size determines the output size",
+			));
+		}
+		match_dims_2d(self)?;
+		let dsize = match_dims_2d(dst)?;
+		let src = core::_InputArray::from_mat(self)?;
+		let mut dst = core::_OutputArray::from_mat_mut(dst)?;
+		crate::imgproc::resize(&src, &mut dst, dsize, 0., 0., interp as i32)
+	}
+
+	/// Returns a new binary mask of the single-channel `Mat` where the elements greater than `thresh` are set to `maxval` and
+	/// the rest to zero, see `imgproc::threshold()` with `THRESH_BINARY`
+	#[cfg(ocvrs_has_module_imgproc)]
+	fn threshold_binary(&self, thresh: f64, maxval: f64) -> Result<Mat>
+	where
+		Self: Sized,
+	{
+		let channels = self.channels();
+		if channels != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have a single channel for this operation, but it has: {channels}"),
+			));
+		}
+		let src = core::_InputArray::from_mat(self)?;
+		let mut out = Mat::default();
+		crate::imgproc::threshold(&src, &mut out, thresh, maxval, crate::imgproc::THRESH_BINARY)?;
+		Ok(out)
+	}
+
+	/// Returns a `CV_8UC1` mask of the same size as the 2-dimensional `Mat` where the 4-connected region around `seed` is set to
+	/// 255 and the rest to zero, see `imgproc::flood_fill_mask()` with `FLOODFILL_MASK_ONLY`
+	///
+	/// A neighbor joins the region if its value is within `lo_diff` below and `up_diff` above the value of the already
+	/// included pixel. The `Mat` itself is not modified.
+	#[cfg(ocvrs_has_module_imgproc)]
+	fn flood_fill_region(&self, seed: Point, lo_diff: Scalar, up_diff: Scalar) -> Result<Mat>
+	where
+		Self: Sized,
+	{
+		let size = match_dims_2d(self)?;
+		if !Rect::from_point_size(Point::new(0, 0), size).contains(seed) {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Seed point: {seed:?} is outside of the Mat with size: {size:?}"),
+			));
+		}
+		// shallow copy because flood_fill_mask() requires a mutable image, it's not written to with FLOODFILL_MASK_ONLY
+		let mut image = Mat::copy(self)?.reference;
+		// the mask must be 2 pixels larger than the image in both dimensions
+		let mut mask = Mat::new_rows_cols_with_default(size.height + 2, size.width + 2, u8::opencv_type(), Scalar::all(0.))?;
+		let flags = 4 | crate::imgproc::FLOODFILL_MASK_ONLY | (255 << 8);
+		crate::imgproc::flood_fill_mask(
+			&mut image,
+			&mut mask,
+			seed,
+			Scalar::default(),
+			&mut Rect::default(),
+			lo_diff,
+			up_diff,
+			flags,
+		)?;
+		Mat::roi(&mask, Rect::new(1, 1, size.width, size.height))?.try_clone()
+	}
+
+	/// Splits a multi-channel `Mat` into separate single-channel `Mat`s, one per channel, see `core::split()`
+	fn split_channels(&self) -> Result<Vec<Mat>>
+	where
+		Self: Sized,
+	{
+		let src = core::_InputArray::from_mat(self)?;
+		let mut planes = core::Vector::<Mat>::new();
+		core::split(&src, &mut planes)?;
+		Ok(planes.to_vec())
+	}
+
+	/// Calculates a non-cryptographic hash of the `Mat` contents including its type and shape
+	///
+	/// Useful for cheap change detection, the value is only stable within a single build of the program.
+	fn content_hash(&self) -> Result<u64> {
+		let mut hasher = DefaultHasher::new();
+		self.typ().hash(&mut hasher);
+		self.mat_size().deref().hash(&mut hasher);
+		if self.empty() {
+			return Ok(hasher.finish());
+		}
+		if self.is_continuous() {
+			hasher.write(self.data_bytes()?);
+		} else if self.dims() == 2 {
+			let strided = self.strided_bytes()?;
+			for row in 0..strided.rows {
+				// safe because the row pointers and sizes are derived from the `Mat` layout
+				hasher.write(unsafe { slice::from_raw_parts(strided.ptr.add(row * strided.stride), strided.row_bytes) });
+			}
+		} else {
+			hasher.write(self.try_clone()?.data_bytes()?);
+		}
+		Ok(hasher.finish())
+	}
+
+	#[inline]
+	fn data_typed<T: DataType>(&self) -> Result<&[T]> {
+		match_format::<T>(self.typ())
+			.and_then(|_| match_is_continuous(self))
+			.and_then(|_| unsafe { self.data_typed_unchecked() })
+	}
+
+	/// Like `Mat::data_typed()`, but returns `None` instead of constructing an `Error` when `T` doesn't match the `Mat` type or
+	/// `Mat` is not continuous
+	#[inline]
+	fn as_slice<T: DataType>(&self) -> Option<&[T]> {
+		if self.typ() == T::opencv_type() && self.is_continuous() {
+			unsafe { self.data_typed_unchecked() }.ok()
+		} else {
+			None
+		}
+	}
+
+	/// # Safety
+	/// Caller must ensure that the `T` type argument corresponds to the data stored in the `Mat` and `Mat` is continuous
+	#[inline]
+	unsafe fn data_typed_unchecked<T: DataType>(&self) -> Result<&[T]> {
+		let data = self.data();
+		Ok(if data.is_null() {
+			&[]
+		} else {
+			slice::from_raw_parts(data.cast::<T>(), self.total())
+		})
+	}
+
+	/// Copies all elements of a `Mat` into the `dst` slice, the length of which must be equal to `Mat::total()`
+	///
+	/// Non-continuous `Mat`s are copied row by row.
+	fn copy_to_slice<T: DataType>(&self, dst: &mut [T]) -> Result<()> {
+		match_format::<T>(self.typ())?;
+		let total = self.total();
+		if dst.len() != total {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"The length of the destination slice: {} must match the total number of Mat elements: {total} exactly",
+					dst.len()
+				),
+			));
+		}
+		if total == 0 {
+			return Ok(());
+		}
+		if self.is_continuous() {
+			dst.copy_from_slice(unsafe { self.data_typed_unchecked()? });
+		} else {
+			let size = match_dims_2d(self)?;
+			// safe because Mat size can't be negative
+			let width = size.width as usize;
+			for (row_n, dst_row) in (0..size.height).zip(dst.chunks_exact_mut(width)) {
+				dst_row.copy_from_slice(unsafe { self.at_row_unchecked(row_n)? });
+			}
+		}
+		Ok(())
+	}
+
+	/// Copies all elements of a `Mat` into a new flat `Vec` in row-major order
+	///
+	/// Non-continuous 2-dimensional `Mat`s are copied row by row.
+	fn to_vec_flat<T: DataType>(&self) -> Result<Vec<T>> {
+		match_format::<T>(self.typ())?;
+		let mut out = Vec::with_capacity(self.total());
+		if self.empty() {
+			return Ok(out);
+		}
+		if self.is_continuous() {
+			out.extend_from_slice(unsafe { self.data_typed_unchecked()? });
+		} else if self.dims() == 2 {
+			for row_n in 0..self.rows() {
+				out.extend_from_slice(unsafe { self.at_row_unchecked(row_n)? });
+			}
+		} else {
+			out.extend_from_slice(self.try_clone()?.data_typed()?);
+		}
+		Ok(out)
+	}
+
+	fn to_vec_2d<T: DataType>(&self) -> Result<Vec<Vec<T>>> {
+		match_format::<T>(self.typ()).and_then(|_| {
+			let size = match_dims_2d(self)?;
+			// safe because Mat size can't be negative
+			let width = size.width as usize;
+			if self.is_continuous() {
+				let data = self.data_typed()?;
+				Ok((0..size.height)
+					.map(|row_n| {
+						// safe because the iteration starts from 0
+						let row_n = row_n as usize;
+						let mut row = Vec::with_capacity(width);
+						row.extend_from_slice(&data[row_n * width..(row_n + 1) * width]);
+						row
+					})
+					.collect())
+			} else {
+				Ok((0..size.height)
+					.map(|row_n| {
+						self.at_row(row_n).map(|src_row| {
+							let mut row = Vec::with_capacity(width);
+							row.extend_from_slice(src_row);
+							row
+						})
+					})
+					.collect::<Result<_>>()?)
+			}
+		})
+	}
+
+	/// Copies a 2-dimensional `Mat` of exactly `R` rows and `C` columns into a stack-allocated array of rows
+	fn to_array_2d<T: DataType, const R: usize, const C: usize>(&self) -> Result<[[T; C]; R]> {
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		// safe because Mat size can't be negative
+		if (size.height as usize, size.width as usize) != (R, C) {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"Mat size: {}x{} doesn't match the requested array size: {R}x{C}",
+					size.height, size.width
+				),
+			));
+		}
+		let mut rows = self.rows_typed::<T>()?;
+		Ok(array::from_fn(|_| {
+			let row = rows.next().expect("Mat row count is checked above");
+			array::from_fn(|col| row[col])
+		}))
+	}
+
+	fn to_vec_3d<T: DataType>(&self) -> Result<Vec<Vec<Vec<T>>>> {
+		match_format::<T>(self.typ())?;
+		let [planes, rows, cols] = match *self.mat_size() {
+			[planes, rows, cols] => [planes, rows, cols],
+			ref mat_size => {
+				return Err(Error::new(
+					core::StsUnmatchedSizes,
+					format!(
+						"Mat must have 3 dimensions for this operation, but it has: {}",
+						mat_size.len()
+					),
+				))
+			}
+		};
+		if self.is_continuous() {
+			let data = unsafe { self.data_typed_unchecked::<T>() }?;
+			// safe because Mat size can't be negative
+			let (rows_usize, cols_usize) = (rows as usize, cols as usize);
+			Ok((0..planes as usize)
+				.map(|plane_n| {
+					(0..rows_usize)
+						.map(|row_n| {
+							let start = (plane_n * rows_usize + row_n) * cols_usize;
+							data[start..start + cols_usize].to_vec()
+						})
+						.collect()
+				})
+				.collect())
+		} else {
+			(0..planes)
+				.map(|plane_n| {
+					(0..rows)
+						.map(|row_n| {
+							(0..cols)
+								// safe because the indices are within the Mat bounds and the type is checked above
+								.map(|col_n| unsafe { self.at_nd_unchecked::<T>(&[plane_n, row_n, col_n]) }.copied())
+								.collect::<Result<_>>()
+						})
+						.collect::<Result<_>>()
+				})
+				.collect()
+		}
+	}
+
+	/// Returns a 2-dimensional [ndarray::ArrayView2] that borrows the `Mat` data without copying
+	///
+	/// Non-continuous `Mat`s produce a strided view.
+	#[cfg(feature = "ndarray")]
+	#[inline]
+	fn as_ndarray_view<T: DataType>(&self) -> Result<ndarray::ArrayView2<T>> {
+		use ndarray::ShapeBuilder;
+
+		match_format::<T>(self.typ())?;
+		let (shape, row_stride) = ndarray_shape_row_stride(self)?;
+		let data = self.data();
+		Ok(if data.is_null() {
+			// Mat without data has no elements, but the shape is still reported, e.g. 5x0
+			unsafe { ndarray::ArrayView2::from_shape_ptr(shape, ptr::NonNull::<T>::dangling().as_ptr()) }
+		} else {
+			// the type is checked above and the shape and strides are taken from the Mat itself
+			unsafe { ndarray::ArrayView2::from_shape_ptr(shape.strides((row_stride, 1)), data.cast::<T>()) }
+		})
+	}
+
+	/// Converts an 8-bit 3-channel 2-dimensional `Mat` into an [image::RgbImage]
+	///
+	/// OpenCV stores color images in the BGR channel order, so the first and the third channels are swapped during the
+	/// conversion. See [Mat::from_image_rgb()] for the reverse operation.
+	#[cfg(feature = "image")]
+	fn to_image_rgb(&self) -> Result<image::RgbImage> {
+		match_format::<core::Vec3b>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		// safe because Mat size can't be negative
+		let mut out = image::RgbImage::new(size.width as u32, size.height as u32);
+		let row_len = size.width as usize * 3;
+		if row_len > 0 {
+			for (row_n, dst_row) in (0..size.height).zip(out.chunks_exact_mut(row_len)) {
+				for (dst, src) in dst_row.chunks_exact_mut(3).zip(self.at_row::<core::Vec3b>(row_n)?) {
+					dst.copy_from_slice(&[src[2], src[1], src[0]]);
+				}
+			}
+		}
+		Ok(out)
+	}
+
+	/// Copies a single-channel 2-dimensional `Mat` of size `R`×`C` into a [nalgebra::SMatrix]
+	///
+	/// The elements are copied from the row-major `Mat` into the column-major `nalgebra` storage.
+	#[cfg(feature = "nalgebra")]
+	fn to_nalgebra<T: DataType + nalgebra::Scalar, const R: usize, const C: usize>(&self) -> Result<nalgebra::SMatrix<T, R, C>> {
+		let channels = self.channels();
+		if channels != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have a single channel for this operation, but it has: {channels}"),
+			));
+		}
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		// safe because Mat size can't be negative
+		if (size.height as usize, size.width as usize) != (R, C) {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"Mat size: {}x{} doesn't match the requested matrix size: {R}x{C}",
+					size.height, size.width
+				),
+			));
+		}
+		let rows = self.rows_typed::<T>()?.collect::<Vec<_>>();
+		Ok(nalgebra::SMatrix::from_fn(|row, col| rows[row][col]))
+	}
+
+	/// Reinterprets the data of a continuous `Mat` as a slice of [bytemuck::Pod] values
+	///
+	/// The byte length of the `Mat` data must be a multiple of `size_of::<P>()` and the data must be suitably aligned for `P`.
+	#[cfg(feature = "bytemuck")]
+	#[inline]
+	fn cast_slice<P: bytemuck::Pod>(&self) -> Result<&[P]> {
+		let bytes = self.data_bytes()?;
+		let size = std::mem::size_of::<P>();
+		if size == 0 || bytes.len() % size != 0 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!(
+					"Mat data length: {} is not a multiple of the target type size: {size}",
+					bytes.len()
+				),
+			));
+		}
+		bytemuck::try_cast_slice(bytes).map_err(|e| Error::new(core::StsBadArg, format!("Can't cast Mat data: {e}")))
+	}
+
+	/// Returns a view of the `Mat` data reinterpreted as elements of type `T` without copying
+	///
+	/// The size of `T` must be equal to the element size of the `Mat`, e.g. a 32-bit float `Mat` can be viewed as `Vec4b`. Unlike
+	/// `Mat::convert_to()` the values are not transformed.
+	fn view_as<T: DataType>(&self) -> Result<BoxedRef<Mat>> {
+		let elem_size = self.elem_size()?;
+		let type_size = std::mem::size_of::<T>();
+		if elem_size != type_size {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat element size: {elem_size} doesn't match the size of the requested type: {type_size}"),
+			));
+		}
+		let mat_size = self.mat_size();
+		let data = self.data();
+		if mat_size.is_empty() {
+			return Mat::new_rows_cols_with_default(0, 0, T::opencv_type(), Scalar::all(0.)).map(BoxedRef::from);
+		}
+		if data.is_null() {
+			// a Mat without elements has no data, but its shape is still kept, e.g. 5x0
+			return Mat::new_nd_with_default(&mat_size, T::opencv_type(), Scalar::all(0.)).map(BoxedRef::from);
+		}
+		let mat_step = self.mat_step();
+		// safe because the Mat is not empty so it has at least 1 dimension
+		let steps = (0..mat_size.len() as i32 - 1).map(|i| mat_step.get(i)).collect::<Vec<_>>();
+		// the data is borrowed for the lifetime of the returned BoxedRef and the element size is checked above
+		unsafe { Mat::new_nd_with_data_unsafe(&mat_size, T::opencv_type(), data.cast_mut().cast::<c_void>(), Some(&steps)) }
+			.map(BoxedRef::from)
+	}
+
+	/// Returns an iterator over `Mat` elements and their positions starting from the last element
+	#[inline]
+	fn iter_rev<T: DataType>(&self) -> Result<MatIterRev<T>>
+	where
+		Self: Sized,
+	{
+		self.iter().map(|inner| MatIterRev { inner })
+	}
+
+	/// Returns an iterator over the complete read-only rows of a 2-dimensional `Mat`
+	///
+	/// No data is copied, the row slices borrow the `Mat` memory directly. Works for both continuous and non-continuous `Mat`s.
+	#[inline]
+	fn rows_typed<T: DataType>(&self) -> Result<MatRowsIter<T>> {
+		let size = match_format::<T>(self.typ()).and_then(|_| match_dims_2d(self))?;
+		// safe because Mat size can't be negative
+		Ok(unsafe { MatRowsIter::new(self, size.width as usize) })
+	}
+
+	/// Returns an iterator over the values of a single channel `ch` of a 2-dimensional multichannel `Mat`
+	///
+	/// `T` is the type of a single channel value, e.g. `u8` for `Vec3b` `Mat`. No data is copied.
+	fn channel_view<T: DataType>(&self, ch: i32) -> Result<MatChannels<T>> {
+		let channels = self.channels();
+		match_format::<T>(core::CV_MAKETYPE(self.depth(), 1))?;
+		if !(0..channels).contains(&ch) {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Channel: {ch} out of bounds 0..{channels}"),
+			));
+		}
+		let size = match_dims_2d(self)?;
+		// safe because Mat size and channel count can't be negative
+		let (channels, ch) = (channels as usize, ch as usize);
+		Ok(MatChannels {
+			rows: unsafe { MatRowsIter::new(self, size.width as usize * channels) },
+			channels,
+			ch,
+			row: None,
+		})
+	}
+
+	/// Returns the borrowed view of the rows of a 2-dimensional `Mat` within the `range`, unbounded ends are clamped to the `Mat`
+	/// bounds
+	#[inline]
+	fn slice_rows(&self, range: impl RangeBounds<i32>) -> Result<BoxedRef<Mat>> {
+		let size = match_dims_2d(self)?;
+		let (start, end) = match_range_bounds(&range, size.height)?;
+		self.row_bounds(start, end)
+	}
+
+	/// Returns the borrowed view of the largest centered region of a 2-dimensional `Mat` that has the aspect ratio of
+	/// `aspect_w:aspect_h`
+	fn center_crop_aspect(&self, aspect_w: i32, aspect_h: i32) -> Result<BoxedRef<Mat>>
+	where
+		Self: Sized,
+	{
+		if aspect_w <= 0 || aspect_h <= 0 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Aspect ratio: {aspect_w}:{aspect_h} must be positive"),
+			));
+		}
+		let size = match_dims_2d(self)?;
+		let (width, height) = (i64::from(size.width), i64::from(size.height));
+		let (aspect_w, aspect_h) = (i64::from(aspect_w), i64::from(aspect_h));
+		let (crop_width, crop_height) = if width * aspect_h > height * aspect_w {
+			(height * aspect_w / aspect_h, height)
+		} else {
+			(width, width * aspect_h / aspect_w)
+		};
+		// safe because the crop dimensions never exceed the Mat dimensions which fit i32
+		let crop = Rect::new(
+			((width - crop_width) / 2) as i32,
+			((height - crop_height) / 2) as i32,
+			crop_width as i32,
+			crop_height as i32,
+		);
+		Mat::roi(self, crop)
+	}
+
+	/// Returns the borrowed view of the columns of a 2-dimensional `Mat` within the `range`, unbounded ends are clamped to the
+	/// `Mat` bounds
+	#[inline]
+	fn slice_cols(&self, range: impl RangeBounds<i32>) -> Result<BoxedRef<Mat>> {
+		let size = match_dims_2d(self)?;
+		let (start, end) = match_range_bounds(&range, size.width)?;
+		self.col_bounds(start, end)
+	}
+
+	/// Returns an iterator over the rows of a 2-dimensional `Mat` as borrowed 1×cols `Mat` views, see [MatTraitConst::row()]
+	///
+	/// No data is copied, empty `Mat`s produce an empty iterator.
+	#[inline]
+	fn row_views(&self) -> Result<MatViews<Self>> {
+		MatViews::new(self, Self::row, |size| size.height)
+	}
+
+	/// Returns an iterator over the columns of a 2-dimensional `Mat` as borrowed rows×1 `Mat` views, see [MatTraitConst::col()]
+	///
+	/// No data is copied, empty `Mat`s produce an empty iterator.
+	#[inline]
+	fn col_views(&self) -> Result<MatViews<Self>> {
+		MatViews::new(self, Self::col, |size| size.width)
+	}
+
+	/// Returns an iterator over all windows of the specified `size` that fully fit into a 2-dimensional `Mat`
+	///
+	/// Each item is the top-left anchor of the window and the borrowed ROI, the windows are yielded in the row-major order.
+	fn windows(&self, size: Size) -> Result<MatWindows<Self>>
+	where
+		Self: Sized,
+	{
+		let mat_size = match_dims_2d(self)?;
+		if size.width <= 0 || size.height <= 0 {
+			return Err(Error::new(core::StsBadArg, format!("Window size: {size:?} must be positive")));
+		}
+		if size.width > mat_size.width || size.height > mat_size.height {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Window size: {size:?} is larger than the Mat size: {mat_size:?}"),
+			));
+		}
+		Ok(MatWindows {
+			mat: self,
+			size,
+			max: Point::new(mat_size.width - size.width, mat_size.height - size.height),
+			pos: Point::new(0, 0),
+		})
+	}
+
+	/// Like `Mat::reshape_nd()`, but validates the new shape before delegating to OpenCV
+	///
+	/// The product of `new_sizes` and the new channel count must be equal to the number of channel values in the `Mat`. Pass 0
+	/// as `channels` to keep the current channel count. The `Mat` must be continuous.
+	fn reshape_checked(&self, channels: i32, new_sizes: &[i32]) -> Result<BoxedRef<Mat>> {
+		if !self.is_continuous() {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				"Mat must be continuous (is_continuous() must be true) to be reshaped",
+			));
+		}
+		let channels = match channels {
+			0 => self.channels(),
+			channels if channels < 0 => {
+				return Err(Error::new(
+					core::StsBadArg,
+					format!("Channel count: {channels} must not be negative"),
+				))
+			}
+			channels => channels,
+		};
+		let mut new_total = u64::try_from(channels)?;
+		for (i, &size) in new_sizes.iter().enumerate() {
+			let size =
+				u64::try_from(size).map_err(|_| Error::new(core::StsOutOfRange, format!("Dimension {i} must not be negative")))?;
+			new_total = new_total.saturating_mul(size);
+		}
+		// safe because Mat::channels() is always positive
+		let total = u64::try_from(self.total())?.saturating_mul(self.channels() as u64);
+		if new_total != total {
+			return Err(Error::new(
+				core::StsUnmatchedSizes,
+				format!(
+					"The new shape: {new_sizes:?} with {channels} channels contains {new_total} values, but the Mat contains {total}"
+				),
+			));
+		}
+		self.reshape_nd(channels, new_sizes)
+	}
+
+	/// 2-dimensional version of [MatTraitConstManual::reshape_checked()] that takes the new shape as a `Size`
+	#[inline]
+	fn reshape_to(&self, channels: i32, size: Size) -> Result<BoxedRef<Mat>> {
+		self.reshape_checked(channels, &[size.height, size.width])
+	}
+
+	/// Returns a single-line JSON object describing the `Mat` with the `type`, `rows`, `cols`, `channels` and `is_continuous`
+	/// fields
+	///
+	/// The `type` field contains the type name as returned by [core::type_to_string()], e.g. `"CV_8UC3"`, or the numeric type
+	/// code if the name can't be obtained (e.g. on OpenCV 3.2). For 2-dimensional `Mat`s with at most 100 channel values a
+	/// `data` field is also included, it contains an array per row with the channel values of that row. Use
+	/// [MatTraitConstManual::debug_with_limit()] to inspect the data of larger `Mat`s.
+	fn to_json_summary(&self) -> String {
+		let typ = self.typ();
+		#[cfg(not(ocvrs_opencv_branch_32))]
+		let typ = core::type_to_string(typ).map_or_else(|_| typ.to_string(), |name| format!("\"{name}\""));
+		#[cfg(ocvrs_opencv_branch_32)]
+		let typ = typ.to_string();
+		let channels = self.channels();
+		let mut out = format!(
+			r#"{{"type":{typ},"rows":{},"cols":{},"channels":{channels},"is_continuous":{}"#,
+			self.rows(),
+			self.cols(),
+			self.is_continuous()
+		);
+		// safe because Mat::channels() is always positive
+		let value_count = self.total().saturating_mul(channels as usize);
+		if self.dims() == 2 && value_count <= JSON_SUMMARY_MAX_VALUES {
+			if let Ok(strided) = self.strided_bytes() {
+				let depth = self.depth();
+				let channel_size = self.elem_size1();
+				out.push_str(r#","data":["#);
+				for row_n in 0..strided.rows {
+					if row_n > 0 {
+						out.push(',');
+					}
+					out.push('[');
+					// the row is within the Mat bounds and contains exactly `row_bytes` bytes
+					let row = unsafe { slice::from_raw_parts(strided.ptr.add(row_n * strided.stride), strided.row_bytes) };
+					for (i, bytes) in row.chunks_exact(channel_size).enumerate() {
+						if i > 0 {
+							out.push(',');
+						}
+						push_json_channel_value(&mut out, depth, bytes);
+					}
+					out.push(']');
+				}
+				out.push(']');
+			}
+		}
+		out.push('}');
+		out
+	}
+
+	/// Returns an adapter that formats the `Mat` like its `Debug` implementation, but also includes up to `max` channel values
+	/// of its data
+	///
+	/// When the `Mat` contains more values, only the first and the last few of them are printed separated by an ellipsis.
+	#[inline]
+	fn debug_with_limit(&self, max: usize) -> MatDebugWithLimit<Self> {
+		MatDebugWithLimit { mat: self, max }
+	}
+
+	/// Returns a continuous version of the `Mat`, it shares the data if the `Mat` is already continuous and uses
+	/// [MatTraitConstManual::clone_continuous()] otherwise
+	fn continuous_cow(&self) -> Result<MatCow>
+	where
+		Self: Sized,
+	{
+		if self.is_continuous() {
+			Mat::copy(self).map(MatCow::Borrowed)
+		} else {
+			self.clone_continuous().map(MatCow::Owned)
+		}
+	}
+
+	/// Uploads a copy of the `Mat` data into a newly allocated `UMat` with the specified `usage` flags
+	///
+	/// Unlike `Mat::get_umat()` the resulting `UMat` doesn't share the memory with the `Mat`, so the `Mat` can be freely
+	/// modified or dropped while the `UMat` is in use. See [UMatTraitConstManual::to_mat()] for the reverse operation.
+	fn to_umat(&self, usage: core::UMatUsageFlags) -> Result<UMat> {
+		let mut out = UMat::new(usage);
+		self.copy_to(&mut out)?;
+		Ok(out)
+	}
+
+	/// Creates a copy of the `Mat` that is guaranteed to be continuous
+	///
+	/// Non-continuous 2-dimensional `Mat`s are copied row by row into a newly allocated compact `Mat` of the same type and
+	/// shape.
+	fn clone_continuous(&self) -> Result<Mat> {
+		if self.is_continuous() || self.dims() != 2 {
+			return self.try_clone();
+		}
+		let mut out = unsafe { Mat::new_rows_cols(self.rows(), self.cols(), self.typ()) }?;
+		// safe because Mat size can't be negative
+		let row_bytes = self.cols() as usize * self.elem_size()?;
+		if row_bytes > 0 {
+			let src = self.data();
+			let step = self.mat_step().get(0);
+			for (row_n, dst_row) in out.data_bytes_mut()?.chunks_exact_mut(row_bytes).enumerate() {
+				// the row is within the Mat bounds and contains exactly `row_bytes` bytes
+				dst_row.copy_from_slice(unsafe { slice::from_raw_parts(src.add(row_n * step), row_bytes) });
+			}
+		}
+		Ok(out)
+	}
+
+	/// Creates a new `Mat_` of the same shape by applying `f` to every element of `self`
+	///
+	/// Elements are visited in the row-major order, the result is always continuous.
+	fn map_typed<T: DataType, U: DataType>(&self, mut f: impl FnMut(T) -> U) -> Result<Mat_<U>> {
+		match_format::<T>(self.typ())?;
+		let mat_size = self.mat_size();
+		let mut out = if mat_size.is_empty() {
+			Mat::new_rows_cols_with_default(0, 0, U::opencv_type(), Scalar::all(0.))?
+		} else {
+			Mat::new_nd_with_default(&mat_size, U::opencv_type(), Scalar::all(0.))?
+		};
+		if self.total() > 0 {
+			let dst = out.data_typed_mut::<U>()?;
+			let mut apply = |src: &[T], dst: &mut [U]| dst.iter_mut().zip(src).for_each(|(d, &s)| *d = f(s));
+			if self.is_continuous() {
+				apply(self.data_typed()?, dst);
+			} else if self.dims() == 2 {
+				// safe because Mat size can't be negative and the Mat is not empty
+				let width = self.cols() as usize;
+				for (src_row, dst_row) in self.rows_typed()?.zip(dst.chunks_exact_mut(width)) {
+					apply(src_row, dst_row);
+				}
+			} else {
+				apply(self.clone_continuous()?.data_typed()?, dst);
+			}
+		}
+		out.try_into_typed()
+	}
+
+	/// Sums all elements of a single-channel `Mat` accumulating into `f64`
+	///
+	/// Unlike `core::sum()` the computation is performed in Rust without going through `Scalar`.
+	fn sum_as_f64<T: DataType + Into<f64>>(&self) -> Result<f64> {
+		let channels = self.channels();
+		if channels != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have a single channel for this operation, but it has: {channels}"),
+			));
+		}
+		match_format::<T>(self.typ())?;
+		if self.empty() {
+			return Ok(0.);
+		}
+		let sum = |data: &[T]| data.iter().map(|&x| x.into()).sum::<f64>();
+		if self.is_continuous() {
+			self.data_typed().map(sum)
+		} else if self.dims() == 2 {
+			self.rows_typed().map(|rows| rows.map(sum).sum())
+		} else {
+			self.clone_continuous()?.data_typed().map(sum)
+		}
+	}
+
+	/// Copies the main diagonal of a 2-dimensional `Mat`, works for rectangular `Mat`s too
+	fn diag_typed<T: DataType>(&self) -> Result<Vec<T>> {
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		// the type is checked above and the indices are within the Mat bounds
+		(0..size.width.min(size.height))
+			.map(|i| unsafe { self.at_2d_unchecked::<T>(i, i) }.copied())
+			.collect()
+	}
+
+	/// Converts the `Mat` to the element type `U` scaling every value by `alpha` and adding `beta`, see `Mat::convert_to()`
+	///
+	/// `U` must have the same number of channels as the `Mat`.
+	fn convert_to_typed<U: DataType>(&self, alpha: f64, beta: f64) -> Result<Mat_<U>> {
+		let out_channels = U::opencv_channels();
+		let channels = self.channels();
+		if out_channels != channels {
+			return Err(Error::new(
+				core::StsUnmatchedFormats,
+				format!("Requested type has: {out_channels} channels, but the Mat has: {channels}"),
+			));
+		}
+		let mut out = Mat::default();
+		self.convert_to(&mut out, U::opencv_type(), alpha, beta)?;
+		out.try_into_typed()
+	}
+
+	/// Converts a single-channel `Mat` to `f32` and linearly maps its values to the `[0, 1]` range
+	///
+	/// If all the elements are equal the result is filled with zeros.
+	fn normalize_minmax_f32(&self) -> Result<Mat_<f32>> {
+		let channels = self.channels();
+		if channels != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have a single channel for this operation, but it has: {channels}"),
+			));
+		}
+		if self.empty() {
+			return Mat::new_rows_cols_with_default(0, 0, f32::opencv_type(), Scalar::all(0.))?.try_into_typed();
+		}
+		let mut out = Mat::default();
+		self.convert_to(&mut out, f32::opencv_type(), 1., 0.)?;
+		let data = out.data_typed_mut::<f32>()?;
+		let (min, max) = data
+			.iter()
+			.fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &x| (min.min(x), max.max(x)));
+		let range = max - min;
+		if range > 0. {
+			data.iter_mut().for_each(|x| *x = (*x - min) / range);
+		} else {
+			data.fill(0.);
+		}
+		out.try_into_typed()
 	}
 
-	/// Like `Mat::at_3d()` but performs no bounds or type checks
-	/// # Safety
-	/// Caller must ensure that indices are within Mat bounds
-	#[inline]
-	unsafe fn at_3d_unchecked<T: DataType>(&self, i0: i32, i1: i32, i2: i32) -> Result<&T> {
-		self.ptr_3d(i0, i1, i2).map(|ptr| convert_ptr(ptr))
+	/// Counts the elements of a `Mat` for which `pred` returns `true`
+	fn count_matching<T: DataType>(&self, pred: impl Fn(&T) -> bool) -> Result<usize> {
+		match_format::<T>(self.typ())?;
+		if self.empty() {
+			return Ok(0);
+		}
+		let count = |data: &[T]| data.iter().filter(|x| pred(x)).count();
+		if self.is_continuous() {
+			self.data_typed().map(count)
+		} else if self.dims() == 2 {
+			self.rows_typed().map(|rows| rows.map(count).sum())
+		} else {
+			self.clone_continuous()?.data_typed().map(count)
+		}
 	}
 
-	/// Like `Mat::at_nd()` but performs no bounds or type checks
-	/// # Safety
-	/// Caller must ensure that indices are within Mat bounds
-	#[inline]
-	unsafe fn at_nd_unchecked<T: DataType>(&self, idx: &[i32]) -> Result<&T> {
-		self.ptr_nd(idx).map(|ptr| convert_ptr(ptr))
+	/// Returns the positions of all non-zero elements of a single-channel 2-dimensional `Mat` in row-major order, see
+	/// `core::find_non_zero()`
+	fn nonzero_points(&self) -> Result<Vec<Point>> {
+		let channels = self.channels();
+		if channels != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have a single channel for this operation, but it has: {channels}"),
+			));
+		}
+		if self.empty() {
+			return Ok(vec![]);
+		}
+		let depth = self.depth();
+		let channel_size = self.elem_size1();
+		let strided = self.strided_bytes()?;
+		let mut out = vec![];
+		for row_n in 0..strided.rows {
+			// the row is within the Mat bounds and contains exactly `row_bytes` bytes
+			let row = unsafe { slice::from_raw_parts(strided.ptr.add(row_n * strided.stride), strided.row_bytes) };
+			for (col_n, bytes) in row.chunks_exact(channel_size).enumerate() {
+				if channel_to_f64(depth, bytes).ok_or_else(|| unsupported_depth(depth))? != 0. {
+					// safe because the row and column counts of a Mat fit i32
+					out.push(Point::new(col_n as i32, row_n as i32));
+				}
+			}
+		}
+		Ok(out)
 	}
 
-	/// Return a complete read-only row
-	#[inline]
-	fn at_row<T: DataType>(&self, row: i32) -> Result<&[T]> {
-		match_format::<T>(self.typ())
-			.and_then(|_| match_indices(self, &[row, 0]))
-			.and_then(|_| unsafe { self.at_row_unchecked(row) })
+	/// Counts the occurrences of every value in a `CV_8UC1` `Mat`
+	///
+	/// A lightweight alternative to `imgproc::calc_hist()` for this common case.
+	fn histogram_u8(&self) -> Result<[u32; 256]> {
+		match_format::<u8>(self.typ())?;
+		let mut hist = [0; 256];
+		if self.empty() {
+			return Ok(hist);
+		}
+		let mut count = |data: &[u8]| data.iter().for_each(|&x| hist[usize::from(x)] += 1);
+		if self.is_continuous() {
+			count(self.data_typed()?);
+		} else if self.dims() == 2 {
+			self.rows_typed()?.for_each(count);
+		} else {
+			count(self.clone_continuous()?.data_typed()?);
+		}
+		Ok(hist)
 	}
 
-	/// Like `Mat::at_row()` but performs no bounds or type checks
-	/// # Safety
-	/// Caller must ensure that index is within Mat bounds
-	#[inline]
-	unsafe fn at_row_unchecked<T: DataType>(&self, row: i32) -> Result<&[T]> {
-		// safe because Mat::size() can't be negative
-		let width = self.size()?.width as usize;
-		self.ptr(row).map(|row| {
-			if row.is_null() {
-				&[]
+	/// Computes the per-channel mean of a non-empty `Mat` with up to 4 channels accumulating in `f64`
+	///
+	/// A lightweight alternative to `core::mean()`, the unused channels of the result are set to zero.
+	fn mean_f64(&self) -> Result<Scalar> {
+		if self.empty() {
+			return Err(Error::new(core::StsBadArg, "Mat must not be empty for this operation"));
+		}
+		let channels = self.channels();
+		if channels > 4 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have at most 4 channels for this operation, but it has: {channels}"),
+			));
+		}
+		let depth = self.depth();
+		let channel_size = self.elem_size1();
+		let mut sums = [0.; 4];
+		let mut accumulate = |bytes: &[u8]| -> Result<()> {
+			if channels == 1 {
+				for ch in bytes.chunks_exact(channel_size) {
+					sums[0] += channel_to_f64(depth, ch).ok_or_else(|| unsupported_depth(depth))?;
+				}
 			} else {
-				slice::from_raw_parts(convert_ptr(row), width)
+				// safe because Mat::channels() is always positive
+				for (i, ch) in bytes.chunks_exact(channel_size).enumerate() {
+					sums[i % channels as usize] += channel_to_f64(depth, ch).ok_or_else(|| unsupported_depth(depth))?;
+				}
 			}
-		})
+			Ok(())
+		};
+		if self.is_continuous() {
+			accumulate(self.data_bytes()?)?;
+		} else if self.dims() == 2 {
+			let strided = self.strided_bytes()?;
+			for row_n in 0..strided.rows {
+				// the row is within the Mat bounds and contains exactly `row_bytes` bytes
+				let row = unsafe { slice::from_raw_parts(strided.ptr.add(row_n * strided.stride), strided.row_bytes) };
+				accumulate(row)?;
+			}
+		} else {
+			accumulate(self.clone_continuous()?.data_bytes()?)?;
+		}
+		let total = self.total() as f64;
+		Ok(Scalar::from(sums.map(|sum| sum / total)))
 	}
 
-	#[inline]
-	fn is_allocated(&self) -> bool {
-		!self.data().is_null()
+	/// Returns the minimum and the maximum elements of a `Mat` computed in a single pass
+	///
+	/// Unlike `core::min_max_loc()` it works for any `T` that supports comparison and doesn't compute the locations.
+	fn min_max_typed<T: DataType + PartialOrd>(&self) -> Result<(T, T)> {
+		match_format::<T>(self.typ())?;
+		let empty_err = || Error::new(core::StsBadArg, "Can't find the minimum and maximum of an empty Mat");
+		if self.empty() {
+			return Err(empty_err());
+		}
+		let mut min_max: Option<(T, T)> = None;
+		let mut update = |data: &[T]| {
+			for &x in data {
+				min_max = Some(match min_max {
+					Some((min, max)) if x < min => (x, max),
+					Some((min, max)) if x > max => (min, x),
+					Some(min_max) => min_max,
+					None => (x, x),
+				});
+			}
+		};
+		if self.is_continuous() {
+			update(self.data_typed()?);
+		} else if self.dims() == 2 {
+			self.rows_typed()?.for_each(&mut update);
+		} else {
+			update(self.clone_continuous()?.data_typed()?);
+		}
+		min_max.ok_or_else(empty_err)
 	}
 
-	/// Returns underlying data array as byte slice, `Mat` must be continuous
-	#[inline]
-	fn data_bytes(&self) -> Result<&[u8]> {
-		match_is_continuous(self).and_then(|_| {
-			let data = self.data();
-			Ok(if data.is_null() {
-				&[]
-			} else {
-				unsafe { slice::from_raw_parts(data, self.total() * self.elem_size()?) }
-			})
-		})
+	/// Returns the `q`-th quantile of the elements of a non-empty single-channel `Mat`, `q` must be within `[0, 1]`
+	///
+	/// The elements are copied and sorted, so the cost is `O(n log n)` in time and `O(n)` in memory. The element at the index
+	/// `q * (n - 1)` rounded to the nearest integer is returned, e.g. `0.5` gives the median. Incomparable values like NaN are
+	/// treated as equal to any other value, so they make the result unreliable.
+	fn quantile<T: DataType + PartialOrd>(&self, q: f64) -> Result<T> {
+		if !(0. ..=1.).contains(&q) {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Quantile must be within [0, 1], but it's: {q}"),
+			));
+		}
+		let channels = self.channels();
+		if channels != 1 {
+			return Err(Error::new(
+				core::StsBadArg,
+				format!("Mat must have a single channel for this operation, but it has: {channels}"),
+			));
+		}
+		let mut values = self.to_vec_flat::<T>()?;
+		if values.is_empty() {
+			return Err(Error::new(core::StsBadArg, "Can't find the quantile of an empty Mat"));
+		}
+		values.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+		// safe because `q` is within [0, 1] so the index is within the bounds of `values`
+		let idx = (q * (values.len() - 1) as f64).round() as usize;
+		Ok(values[idx])
 	}
 
+	/// Creates a new `Mat` of the same type and shape as `self` with all elements set to zero
 	#[inline]
-	fn data_typed<T: DataType>(&self) -> Result<&[T]> {
-		match_format::<T>(self.typ())
-			.and_then(|_| match_is_continuous(self))
-			.and_then(|_| unsafe { self.data_typed_unchecked() })
+	fn zeros_like(&self) -> Result<Mat> {
+		new_like(self, Scalar::all(0.))
 	}
 
-	/// # Safety
-	/// Caller must ensure that the `T` type argument corresponds to the data stored in the `Mat` and `Mat` is continuous
+	/// Creates a new `Mat` of the same type and shape as `self` with all channels of all elements set to one
+	///
+	/// Unlike `Mat::ones()` this also sets all channels of multichannel `Mat`s, not only the first one.
 	#[inline]
-	unsafe fn data_typed_unchecked<T: DataType>(&self) -> Result<&[T]> {
-		let data = self.data();
-		Ok(if data.is_null() {
-			&[]
-		} else {
-			slice::from_raw_parts(data.cast::<T>(), self.total())
-		})
+	fn ones_like(&self) -> Result<Mat> {
+		new_like(self, Scalar::all(1.))
 	}
 
-	fn to_vec_2d<T: DataType>(&self) -> Result<Vec<Vec<T>>> {
-		match_format::<T>(self.typ()).and_then(|_| {
-			let size = match *self.mat_size() {
-				[rows, cols] => Size::new(cols, rows),
-				ref mat_size => {
-					return Err(Error::new(
-						core::StsUnmatchedSizes,
-						format!(
-							"Mat must have 2 dimensions for this operation, but it has: {}",
-							mat_size.len()
-						),
-					))
-				}
-			};
-			// safe because Mat size can't be negative
-			let width = size.width as usize;
-			if self.is_continuous() {
-				let data = self.data_typed()?;
-				Ok((0..size.height)
-					.map(|row_n| {
-						// safe because the iteration starts from 0
-						let row_n = row_n as usize;
-						let mut row = Vec::with_capacity(width);
-						row.extend_from_slice(&data[row_n * width..(row_n + 1) * width]);
-						row
-					})
-					.collect())
-			} else {
-				Ok((0..size.height)
-					.map(|row_n| {
-						self.at_row(row_n).map(|src_row| {
-							let mut row = Vec::with_capacity(width);
-							row.extend_from_slice(src_row);
-							row
-						})
-					})
-					.collect::<Result<_>>()?)
-			}
-		})
+	/// Returns the position of the first element equal to `needle` or `None` if there is no such element
+	///
+	/// Elements are checked in the same order as `Mat::iter()` yields them, non-continuous `Mat`s are supported.
+	#[inline]
+	fn position_of<T: DataType + PartialEq>(&self, needle: &T) -> Result<Option<Point>>
+	where
+		Self: Sized,
+	{
+		match_format::<T>(self.typ())?;
+		Ok(self.iter::<T>()?.find(|(_, x)| x == needle).map(|(pos, _)| pos))
 	}
 
 	/// Returns an iterator over `Mat` elements and their positions
@@ -564,13 +2837,7 @@ pub trait MatTraitConstManual: MatTraitConst {
 	where
 		Self: Sized,
 	{
-		MatConstIterator::over(self).map_or(
-			Ok(MatIter {
-				iter: None,
-				_d: PhantomData,
-			}),
-			MatIter::new,
-		)
+		MatConstIterator::over(self).map_or(Ok(MatIter::empty()), MatIter::new)
 	}
 
 	#[inline]
@@ -601,6 +2868,42 @@ pub trait MatTraitManual: MatTraitConstManual + MatTrait {
 		self.ptr_2d_mut(row, col).map(|ptr| convert_ptr_mut(ptr))
 	}
 
+	/// Like `Mat::at_2d_mut()`, but returns `None` instead of constructing an `Error` when `T` doesn't match the `Mat` type or
+	/// the indices are out of bounds
+	#[inline]
+	fn get_2d_mut<T: DataType>(&mut self, row: i32, col: i32) -> Option<&mut T> {
+		if is_valid_2d::<T>(self, row, col) {
+			unsafe { self.at_2d_unchecked_mut(row, col) }.ok()
+		} else {
+			None
+		}
+	}
+
+	/// Mutable version of [MatTraitConstManual::elem_ptr()]
+	#[inline]
+	fn elem_ptr_mut<T: DataType>(&mut self, row: i32, col: i32) -> Result<*mut T> {
+		match_format::<T>(self.typ()).and_then(|_| match_indices(self, &[row, col]))?;
+		self.ptr_2d_mut(row, col).map(|ptr| ptr.cast::<T>())
+	}
+
+	/// Returns mutable references to the elements of the main diagonal of a 2-dimensional `Mat`
+	fn diag_typed_mut<T: DataType>(&mut self) -> Result<Vec<&mut T>> {
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		let ptrs = (0..size.width.min(size.height))
+			.map(|i| self.ptr_2d_mut(i, i))
+			.collect::<Result<Vec<_>>>()?;
+		// the type is checked above and every pointer references a distinct element
+		Ok(ptrs.into_iter().map(|ptr| unsafe { convert_ptr_mut(ptr) }).collect())
+	}
+
+	/// Mutable version of [MatTraitConstManual::elem_ptr_nd()]
+	#[inline]
+	fn elem_ptr_nd_mut<T: DataType>(&mut self, idx: &[i32]) -> Result<*mut T> {
+		match_format::<T>(self.typ()).and_then(|_| match_indices(self, idx))?;
+		self.ptr_nd_mut(idx).map(|ptr| ptr.cast::<T>())
+	}
+
 	/// Like `Mat::at_pt_mut()` but performs no bounds or type checks
 	/// # Safety
 	/// Caller must ensure that point is within Mat bounds
@@ -667,6 +2970,17 @@ pub trait MatTraitManual: MatTraitConstManual + MatTrait {
 		unsafe { self.data_typed_unchecked_mut() }
 	}
 
+	/// Like `Mat::data_typed_mut()`, but returns `None` instead of constructing an `Error` when `T` doesn't match the `Mat` type
+	/// or `Mat` is not continuous
+	#[inline]
+	fn as_slice_mut<T: DataType>(&mut self) -> Option<&mut [T]> {
+		if self.typ() == T::opencv_type() && self.is_continuous() {
+			unsafe { self.data_typed_unchecked_mut() }.ok()
+		} else {
+			None
+		}
+	}
+
 	/// # Safety
 	/// Caller must ensure that the `T` type argument corresponds to the data stored in the `Mat` and `Mat` is continuous
 	#[inline]
@@ -680,6 +2994,162 @@ pub trait MatTraitManual: MatTraitConstManual + MatTrait {
 		})
 	}
 
+	/// Returns a 2-dimensional [ndarray::ArrayViewMut2] that borrows the `Mat` data without copying
+	///
+	/// Non-continuous `Mat`s produce a strided view.
+	#[cfg(feature = "ndarray")]
+	#[inline]
+	fn as_ndarray_view_mut<T: DataType>(&mut self) -> Result<ndarray::ArrayViewMut2<T>> {
+		use ndarray::ShapeBuilder;
+
+		match_format::<T>(self.typ())?;
+		let (shape, row_stride) = ndarray_shape_row_stride(self)?;
+		let data = self.data_mut();
+		Ok(if data.is_null() {
+			// Mat without data has no elements, but the shape is still reported, e.g. 5x0
+			unsafe { ndarray::ArrayViewMut2::from_shape_ptr(shape, ptr::NonNull::<T>::dangling().as_ptr()) }
+		} else {
+			// the type is checked above and the shape and strides are taken from the Mat itself
+			unsafe { ndarray::ArrayViewMut2::from_shape_ptr(shape.strides((row_stride, 1)), data.cast::<T>()) }
+		})
+	}
+
+	/// Returns a [rayon] parallel iterator over the mutable rows of a 2-dimensional `Mat`
+	///
+	/// The work is split by rows, not by individual elements. Works for both continuous and non-continuous `Mat`s.
+	#[cfg(feature = "rayon")]
+	fn par_rows_mut<T: DataType + Send>(&mut self) -> Result<rayon::vec::IntoIter<&mut [T]>> {
+		use rayon::iter::IntoParallelIterator;
+
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		// safe because Mat size can't be negative
+		let width = size.width as usize;
+		let row_ptrs = (0..size.height)
+			.map(|row_n| self.ptr_mut(row_n))
+			.collect::<Result<Vec<_>>>()?;
+		let rows = row_ptrs
+			.into_iter()
+			.map(|row_ptr| {
+				if row_ptr.is_null() {
+					&mut []
+				} else {
+					// the type is checked above, the rows don't overlap and are borrowed for the lifetime of `self`
+					unsafe { slice::from_raw_parts_mut(row_ptr.cast::<T>(), width) }
+				}
+			})
+			.collect::<Vec<_>>();
+		Ok(rows.into_par_iter())
+	}
+
+	/// Sets every element of a 2-dimensional `Mat` to the value returned by `f` called with the row and column of that element
+	fn fill_from_fn<T: DataType>(&mut self, mut f: impl FnMut(i32, i32) -> T) -> Result<()> {
+		match_format::<T>(self.typ())?;
+		let size = match_dims_2d(self)?;
+		if self.is_continuous() {
+			// safe because Mat size can't be negative
+			let width = size.width as usize;
+			if width > 0 {
+				let data = unsafe { self.data_typed_unchecked_mut::<T>() }?;
+				for (row_n, row) in data.chunks_exact_mut(width).enumerate() {
+					// safe because the row count of a Mat fits i32
+					let row_n = row_n as i32;
+					for (col_n, x) in row.iter_mut().enumerate() {
+						// safe because the column count of a Mat fits i32
+						*x = f(row_n, col_n as i32);
+					}
+				}
+			}
+		} else {
+			for row_n in 0..size.height {
+				for col_n in 0..size.width {
+					// safe because the indices are within the Mat bounds and the type is checked above
+					*unsafe { self.at_2d_unchecked_mut::<T>(row_n, col_n) }? = f(row_n, col_n);
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Sets all elements of the `Mat` to `value`, or only those with non-zero `mask` elements if it's passed, see
+	/// `Mat::set_to()`
+	///
+	/// `mask` must be a `CV_8UC1` `Mat` of the same size as this `Mat`.
+	fn set_scalar(&mut self, value: Scalar, mask: Option<&impl MatTraitConst>) -> Result<()> {
+		if let Some(mask) = mask {
+			match_format::<u8>(mask.typ())?;
+			if *mask.mat_size() != *self.mat_size() {
+				return Err(Error::new(
+					core::StsUnmatchedSizes,
+					format!(
+						"Mask size: {:?} doesn't match the Mat size: {:?}",
+						&*mask.mat_size(),
+						&*self.mat_size()
+					),
+				));
+			}
+			self.set_to(&value, &core::_InputArray::from_mat(mask)?)?;
+		} else {
+			self.set_to(&value, &core::no_array())?;
+		}
+		Ok(())
+	}
+
+	/// Blends the 2-dimensional `src` into this 2-dimensional `Mat` with its top-left corner placed at `at`, see
+	/// `core::add_weighted()`
+	///
+	/// The resulting elements are `src * alpha + self * (1 - alpha)`, `alpha` must be within `[0, 1]`. The parts of `src` that
+	/// extend past the boundaries of the `Mat` are clipped, so there is nothing to do if they don't overlap at all. Both `Mat`s
+	/// must have the same type.
+	fn overlay_at(&mut self, src: &impl MatTraitConst, at: Point, alpha: f64) -> Result<()> {
+		if !(0. ..=1.).contains(&alpha) {
+			return Err(Error::new(
+				core::StsOutOfRange,
+				format!("Alpha must be within [0, 1], but it's: {alpha}"),
+			));
+		}
+		let typ = self.typ();
+		if src.typ() != typ {
+			return Err(Error::new(
+				core::StsUnmatchedFormats,
+				format!("Source Mat has type: {}, but type: {typ} is expected", src.typ()),
+			));
+		}
+		let size = match_dims_2d(self)?;
+		let src_size = match_dims_2d(src)?;
+		let left = at.x.max(0);
+		let top = at.y.max(0);
+		let right = at.x.saturating_add(src_size.width).min(size.width);
+		let bottom = at.y.saturating_add(src_size.height).min(size.height);
+		if right <= left || bottom <= top {
+			return Ok(());
+		}
+		let src_roi = Mat::roi(src, Rect::new(left - at.x, top - at.y, right - left, bottom - top))?;
+		let mut dst_roi = self.roi_mut(Rect::new(left, top, right - left, bottom - top))?;
+		// core::add_weighted() processes the arrays elementwise so it can work in place
+		unsafe {
+			dst_roi.modify_inplace(|dst_in, dst_out| core::add_weighted(&src_roi, alpha, dst_in, 1. - alpha, 0., dst_out, -1))
+		}
+	}
+
+	/// Replaces every element of a `Mat` with the value returned by `f` called with that element, without reallocating
+	///
+	/// Non-continuous `Mat`s must be 2-dimensional, they are processed row by row.
+	fn apply_in_place<T: DataType>(&mut self, mut f: impl FnMut(T) -> T) -> Result<()> {
+		match_format::<T>(self.typ())?;
+		let mut apply = |data: &mut [T]| data.iter_mut().for_each(|x| *x = f(*x));
+		if self.is_continuous() {
+			apply(unsafe { self.data_typed_unchecked_mut::<T>() }?);
+		} else {
+			let size = match_dims_2d(self)?;
+			for row_n in 0..size.height {
+				// safe because the row is within the Mat bounds and the type is checked above
+				apply(unsafe { self.at_row_unchecked_mut::<T>(row_n) }?);
+			}
+		}
+		Ok(())
+	}
+
 	/// Returns a mutable iterator over `Mat` elements and their positions
 	#[inline]
 	fn iter_mut<T: DataType>(&mut self) -> Result<MatIterMut<T>>
@@ -703,35 +3173,164 @@ impl<T: MatTrait + ?Sized> MatTraitManual for T {}
 input_output_array! { Mat, from_mat, from_mat_mut }
 input_output_array_vector! { Mat, from_mat_vec, from_mat_vec_mut }
 
+fn debug_struct_header<'f, 'a>(
+	mat: &(impl MatTraitConst + ?Sized),
+	f: &'f mut fmt::Formatter<'a>,
+) -> Result<fmt::DebugStruct<'f, 'a>, fmt::Error> {
+	let typ = mat.typ();
+	let depth = mat.depth();
+	#[cfg(not(ocvrs_opencv_branch_32))]
+	let typ = core::type_to_string(typ).map_err(|_| fmt::Error)?;
+	#[cfg(not(ocvrs_opencv_branch_32))]
+	let depth = core::depth_to_string(depth).map_err(|_| fmt::Error)?;
+	let mut out = f.debug_struct("Mat");
+	out.field("type", &typ)
+		.field("flags", &mat.flags())
+		.field("channels", &mat.channels())
+		.field("depth", &depth)
+		.field("dims", &mat.dims())
+		.field("size", &mat.size().map_err(|_| fmt::Error)?)
+		.field("rows", &mat.rows())
+		.field("cols", &mat.cols())
+		.field("elem_size", &mat.elem_size().map_err(|_| fmt::Error)?)
+		.field("elem_size1", &mat.elem_size1())
+		.field("total", &mat.total())
+		.field("is_continuous", &mat.is_continuous())
+		.field("is_submatrix", &mat.is_submatrix());
+	Ok(out)
+}
+
 impl fmt::Debug for Mat {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		let typ = self.typ();
-		let depth = self.depth();
-		#[cfg(not(ocvrs_opencv_branch_32))]
-		let typ = core::type_to_string(typ).map_err(|_| fmt::Error)?;
-		#[cfg(not(ocvrs_opencv_branch_32))]
-		let depth = core::depth_to_string(depth).map_err(|_| fmt::Error)?;
-		f.debug_struct("Mat")
-			.field("type", &typ)
-			.field("flags", &self.flags())
-			.field("channels", &self.channels())
-			.field("depth", &depth)
-			.field("dims", &self.dims())
-			.field("size", &self.size().map_err(|_| fmt::Error)?)
-			.field("rows", &self.rows())
-			.field("cols", &self.cols())
-			.field("elem_size", &self.elem_size().map_err(|_| fmt::Error)?)
-			.field("elem_size1", &self.elem_size1())
-			.field("total", &self.total())
-			.field("is_continuous", &self.is_continuous())
-			.field("is_submatrix", &self.is_submatrix())
-			.finish()
+		debug_struct_header(self, f)?.finish()
+	}
+}
+
+/// `Debug` adapter that also prints the `Mat` data, see [MatTraitConstManual::debug_with_limit()]
+pub struct MatDebugWithLimit<'m, M: ?Sized> {
+	mat: &'m M,
+	max: usize,
+}
+
+impl<M: MatTraitConst + ?Sized> fmt::Debug for MatDebugWithLimit<'_, M> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let mat_cont;
+		let data = if self.mat.is_continuous() {
+			self.mat.data_bytes()
+		} else {
+			mat_cont = self.mat.try_clone().map_err(|_| fmt::Error)?;
+			mat_cont.data_bytes()
+		}
+		.map_err(|_| fmt::Error)?;
+		let data = MatDataDump {
+			depth: self.mat.depth(),
+			data,
+			channel_size: self.mat.elem_size1(),
+			max: self.max,
+		};
+		debug_struct_header(self.mat, f)?.field("data", &data).finish()
+	}
+}
+
+/// Prints the channel values of the `Mat` data, truncating the middle part if there are more than `max` of them
+struct MatDataDump<'d> {
+	depth: i32,
+	data: &'d [u8],
+	channel_size: usize,
+	max: usize,
+}
+
+impl fmt::Debug for MatDataDump<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let depth = self.depth;
+		let values = self
+			.data
+			.chunks_exact(self.channel_size)
+			.map(|bytes| ChannelValue { depth, bytes });
+		let count = values.len();
+		if count <= self.max {
+			f.debug_list().entries(values).finish()
+		} else {
+			let tail = self.max / 2;
+			let head = self.max - tail;
+			f.debug_list()
+				.entries(values.clone().take(head))
+				.entry(&format_args!("..."))
+				.entries(values.skip(count - tail))
+				.finish()
+		}
+	}
+}
+
+/// Maximum number of channel values for which [MatTraitConstManual::to_json_summary()] includes the `data` field
+const JSON_SUMMARY_MAX_VALUES: usize = 100;
+
+/// Appends a single channel value of the specified `depth` stored in `bytes` as a JSON number, non-finite and unsupported
+/// values are written as `null`
+fn push_json_channel_value(out: &mut String, depth: i32, bytes: &[u8]) {
+	let value = match depth {
+		core::CV_8U => bytes.try_into().ok().map(|b| u8::from_ne_bytes(b).to_string()),
+		core::CV_8S => bytes.try_into().ok().map(|b| i8::from_ne_bytes(b).to_string()),
+		core::CV_16U => bytes.try_into().ok().map(|b| u16::from_ne_bytes(b).to_string()),
+		core::CV_16S => bytes.try_into().ok().map(|b| i16::from_ne_bytes(b).to_string()),
+		core::CV_32S => bytes.try_into().ok().map(|b| i32::from_ne_bytes(b).to_string()),
+		core::CV_32F => bytes
+			.try_into()
+			.ok()
+			.map(f32::from_ne_bytes)
+			.filter(|v| v.is_finite())
+			.map(|v| v.to_string()),
+		core::CV_64F => bytes
+			.try_into()
+			.ok()
+			.map(f64::from_ne_bytes)
+			.filter(|v| v.is_finite())
+			.map(|v| v.to_string()),
+		_ => None,
+	};
+	out.push_str(value.as_deref().unwrap_or("null"));
+}
+
+/// Prints a single channel value of the specified `depth` stored in `bytes`
+struct ChannelValue<'b> {
+	depth: i32,
+	bytes: &'b [u8],
+}
+
+impl fmt::Debug for ChannelValue<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let bytes = self.bytes;
+		match self.depth {
+			core::CV_8U => fmt::Debug::fmt(&u8::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			core::CV_8S => fmt::Debug::fmt(&i8::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			core::CV_16U => fmt::Debug::fmt(&u16::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			core::CV_16S => fmt::Debug::fmt(&i16::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			core::CV_32S => fmt::Debug::fmt(&i32::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			core::CV_32F => fmt::Debug::fmt(&f32::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			core::CV_64F => fmt::Debug::fmt(&f64::from_ne_bytes(bytes.try_into().map_err(|_| fmt::Error)?), f),
+			_ => fmt::Debug::fmt(bytes, f),
+		}
 	}
 }
 
 input_output_array! { UMat, from_umat, from_umat_mut }
 input_output_array_vector! { UMat, from_umat_vec, from_umat_vec_mut }
 
+pub trait UMatTraitConstManual: UMatTraitConst {
+	/// Downloads a copy of the `UMat` data into a newly allocated `Mat`
+	///
+	/// Unlike `UMat::get_mat()` the resulting `Mat` doesn't map the `UMat` memory, so the `UMat` stays usable by OpenCL
+	/// operations while the `Mat` is alive. See [MatTraitConstManual::to_umat()] for the reverse operation.
+	#[inline]
+	fn to_mat(&self) -> Result<Mat> {
+		let mut out = Mat::default();
+		self.copy_to(&mut out)?;
+		Ok(out)
+	}
+}
+
+impl<T: UMatTraitConst + ?Sized> UMatTraitConstManual for T {}
+
 impl Deref for MatSize {
 	type Target = [i32];
 
@@ -785,6 +3384,21 @@ pub trait MatConstIteratorTraitManual: MatConstIteratorTrait {
 			))
 		}
 	}
+
+	/// Moves the iterator by `ofs` elements relative to the current position
+	///
+	/// Unlike `seek()` it fails with `StsOutOfRange` instead of moving before the first element or past the end of the `Mat`.
+	fn try_seek(&mut self, ofs: isize) -> Result<()> {
+		let pos = self.lpos()?;
+		let total = isize::try_from(self.m().total())?;
+		match pos.checked_add(ofs) {
+			Some(new_pos) if (0..=total).contains(&new_pos) => self.seek(ofs, true),
+			_ => Err(Error::new(
+				core::StsOutOfRange,
+				format!("Seeking by: {ofs} from position: {pos} is out of bounds 0..={total}"),
+			)),
+		}
+	}
 }
 
 impl<T: MatConstIteratorTrait> MatConstIteratorTraitManual for T {}
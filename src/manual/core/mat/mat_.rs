@@ -2,11 +2,12 @@ use std::convert::TryFrom;
 use std::ffi::c_void;
 use std::fmt;
 use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
 
 use crate::boxed_ref::{BoxedRef, BoxedRefMut};
 use crate::core::{
-	Mat, MatTrait, MatTraitConst, MatTraitConstManual, MatTraitManual, Point, ToInputArray, ToInputOutputArray, ToOutputArray,
-	_InputArray, _InputOutputArray, _OutputArray,
+	Mat, MatConstIterator, MatIterMut, MatIterRef, MatTrait, MatTraitConst, MatTraitConstManual, MatTraitManual, Point,
+	ToInputArray, ToInputOutputArray, ToOutputArray, _InputArray, _InputOutputArray, _OutputArray,
 };
 use crate::traits::Boxed;
 use crate::{Error, Result};
@@ -143,6 +144,115 @@ impl<T: DataType> Mat_<T> {
 	}
 }
 
+/// Elementwise comparison of the logical contents, strides and submatrix flags are not taken into account
+///
+/// Elements are compared exactly using the `PartialEq` implementation of `T`, so for floating point types there is no tolerance
+/// and `NaN` elements never compare equal. Use approximate comparison for such cases.
+impl<T: DataType + PartialEq> PartialEq for Mat_<T> {
+	fn eq(&self, other: &Self) -> bool {
+		if *self.mat_size() != *other.mat_size() {
+			return false;
+		}
+		if let (Ok(a), Ok(b)) = (self.data_typed(), other.data_typed()) {
+			a == b
+		} else if let (Ok(a), Ok(b)) = (self.rows_typed::<T>(), other.rows_typed::<T>()) {
+			a.eq(b)
+		} else {
+			// non-continuous Mat with more than 2 dimensions, compare continuous copies
+			match (self.try_clone(), other.try_clone()) {
+				(Ok(a), Ok(b)) => matches!((a.data_typed::<T>(), b.data_typed::<T>()), (Ok(a), Ok(b)) if a == b),
+				_ => false,
+			}
+		}
+	}
+}
+
+/// Element access by `(row, col)` index
+///
+/// # Panics
+/// Panics if the `Mat_` is not 2-dimensional or the index is out of bounds. Use [Mat_::at_2d] for the checked access.
+impl<T: DataType> Index<(i32, i32)> for Mat_<T> {
+	type Output = T;
+
+	#[inline]
+	fn index(&self, (row, col): (i32, i32)) -> &Self::Output {
+		self.at_2d(row, col).unwrap_or_else(|e| panic!("{e}"))
+	}
+}
+
+/// Mutable element access by `(row, col)` index
+///
+/// # Panics
+/// Panics if the `Mat_` is not 2-dimensional or the index is out of bounds. Use [Mat_::at_2d_mut] for the checked access.
+impl<T: DataType> IndexMut<(i32, i32)> for Mat_<T> {
+	#[inline]
+	fn index_mut(&mut self, (row, col): (i32, i32)) -> &mut Self::Output {
+		self.at_2d_mut(row, col).unwrap_or_else(|e| panic!("{e}"))
+	}
+}
+
+/// Collects the elements into a single-column `Mat_` of N×1 shape
+///
+/// # Panics
+/// Panics if the `Mat_` can't be allocated. Use [Mat::from_exact_iter] for the fallible construction.
+impl<T: DataType> FromIterator<T> for Mat_<T> {
+	fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+		let data = iter.into_iter().collect::<Vec<_>>();
+		let inner = Mat::from_exact_iter(data.into_iter()).unwrap_or_else(|e| panic!("{e}"));
+		Self {
+			inner,
+			_type: PhantomData,
+		}
+	}
+}
+
+/// Appends the elements as new rows of a single-column `Mat_`
+///
+/// Every call copies the elements through `Mat::push_back()` which reallocates the data when the reserved capacity is
+/// exhausted, so the cost is amortized similarly to `Vec::extend()`.
+///
+/// # Panics
+/// Panics if the `Mat_` is not empty and has more than one column or if it can't be reallocated.
+impl<T: DataType> Extend<T> for Mat_<T> {
+	fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+		let data = iter.into_iter().collect::<Vec<_>>();
+		if data.is_empty() {
+			return;
+		}
+		Mat::from_exact_iter(data.into_iter())
+			.and_then(|tail| self.inner.push_back(&tail))
+			.unwrap_or_else(|e| panic!("{e}"));
+	}
+}
+
+/// Iterates over references to the elements and their positions, empty `Mat_` produces an empty iterator
+impl<'m, T: DataType> IntoIterator for &'m Mat_<T> {
+	type Item = (Point, &'m T);
+	type IntoIter = MatIterRef<'m, T>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		MatConstIterator::over(self).and_then(MatIterRef::new).unwrap_or(MatIterRef {
+			iter: None,
+			_d: PhantomData,
+		})
+	}
+}
+
+/// Iterates over mutable references to the elements and their positions, empty `Mat_` produces an empty iterator
+impl<'m, T: DataType> IntoIterator for &'m mut Mat_<T> {
+	type Item = (Point, &'m mut T);
+	type IntoIter = MatIterMut<'m, T>;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter_mut().unwrap_or(MatIterMut {
+			iter: None,
+			_d: PhantomData,
+		})
+	}
+}
+
 impl<T> MatTraitConst for Mat_<T> {
 	#[inline]
 	fn as_raw_Mat(&self) -> *const c_void {
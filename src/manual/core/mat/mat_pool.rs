@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::core::{Mat, Scalar};
+use crate::prelude::*;
+use crate::Result;
+
+type PoolKey = (i32, i32, i32);
+
+/// Pool of reusable `Mat` allocations keyed by their rows, columns and type
+///
+/// Use [MatPool::get()] to obtain a [PooledMat] that is returned to the pool when dropped, that way the subsequent requests
+/// for the `Mat` with the same geometry can skip the allocation. The pool is thread-safe, all access to the stored `Mat`s
+/// happens behind a `Mutex`.
+#[derive(Default)]
+pub struct MatPool {
+	free: Mutex<HashMap<PoolKey, Vec<Mat>>>,
+}
+
+impl MatPool {
+	/// Creates a new empty pool
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Returns a 2-dimensional `Mat` with the specified `rows`, `cols` and `typ` taking a previously freed one from the pool
+	/// if available
+	///
+	/// A freshly allocated `Mat` is filled with zeros, a recycled `Mat` retains the data of its previous usage.
+	pub fn get(&self, rows: i32, cols: i32, typ: i32) -> Result<PooledMat> {
+		let recycled = self.lock().get_mut(&(rows, cols, typ)).and_then(|mats| mats.pop());
+		let mat = match recycled {
+			Some(mat) => mat,
+			None => Mat::new_rows_cols_with_default(rows, cols, typ, Scalar::all(0.))?,
+		};
+		Ok(PooledMat {
+			mat: Some(mat),
+			pool: self,
+		})
+	}
+
+	/// Returns the number of `Mat`s currently available for reuse
+	pub fn available(&self) -> usize {
+		self.lock().values().map(|mats| mats.len()).sum()
+	}
+
+	/// Frees all the `Mat`s stored in the pool
+	pub fn clear(&self) {
+		self.lock().clear();
+	}
+
+	fn put(&self, mat: Mat) {
+		if mat.dims() == 2 && !mat.empty() {
+			self.lock().entry((mat.rows(), mat.cols(), mat.typ())).or_default().push(mat);
+		}
+	}
+
+	fn lock(&self) -> MutexGuard<HashMap<PoolKey, Vec<Mat>>> {
+		// the pool only caches allocations so its contents are still usable even if another thread panicked
+		self.free.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+}
+
+impl fmt::Debug for MatPool {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("MatPool").field("available", &self.available()).finish()
+	}
+}
+
+/// `Mat` borrowed from a [MatPool], returned to the pool on drop
+///
+/// The `Mat` is stored back under its geometry at the time of the drop, so it's fine to reallocate it while it's in use.
+pub struct PooledMat<'p> {
+	mat: Option<Mat>,
+	pool: &'p MatPool,
+}
+
+impl PooledMat<'_> {
+	/// Takes the `Mat` out of the pool permanently, it won't be returned on drop
+	#[inline]
+	pub fn into_inner(mut self) -> Mat {
+		self.mat.take().expect("Mat is only taken on drop")
+	}
+}
+
+impl Deref for PooledMat<'_> {
+	type Target = Mat;
+
+	#[inline]
+	fn deref(&self) -> &Self::Target {
+		self.mat.as_ref().expect("Mat is only taken on drop")
+	}
+}
+
+impl DerefMut for PooledMat<'_> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.mat.as_mut().expect("Mat is only taken on drop")
+	}
+}
+
+impl Drop for PooledMat<'_> {
+	fn drop(&mut self) {
+		if let Some(mat) = self.mat.take() {
+			self.pool.put(mat);
+		}
+	}
+}
+
+impl fmt::Debug for PooledMat<'_> {
+	#[inline]
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Debug::fmt(&**self, f)
+	}
+}
@@ -0,0 +1,72 @@
+use serde::{de, ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::core::{Mat, MatTraitConst, MatTraitConstManual, MatTraitManual, Scalar};
+
+/// Serialized representation of a `Mat`: dimensions, type and continuous data bytes
+#[derive(Serialize)]
+struct MatRef<'m> {
+	sizes: &'m [i32],
+	typ: i32,
+	data: &'m [u8],
+}
+
+/// Owned counterpart of [MatRef] used for deserialization
+#[derive(Deserialize)]
+struct MatOwned {
+	sizes: Vec<i32>,
+	typ: i32,
+	data: Vec<u8>,
+}
+
+impl Serialize for Mat {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		let continuous;
+		let mat = if self.is_continuous() {
+			self
+		} else {
+			continuous = self.try_clone().map_err(ser::Error::custom)?;
+			&continuous
+		};
+		let mat_size = mat.mat_size();
+		MatRef {
+			sizes: &mat_size,
+			typ: mat.typ(),
+			data: mat.data_bytes().map_err(ser::Error::custom)?,
+		}
+		.serialize(serializer)
+	}
+}
+
+impl<'de> Deserialize<'de> for Mat {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		let MatOwned { sizes, typ, data } = MatOwned::deserialize(deserializer)?;
+		if sizes.is_empty() {
+			return if data.is_empty() {
+				Ok(Mat::default())
+			} else {
+				Err(de::Error::custom(format!(
+					"Mat without dimensions must not have data, but it has: {} bytes",
+					data.len()
+				)))
+			};
+		}
+		// validate against the data before allocating so that the input can't request an arbitrarily large Mat
+		let (_, elem_size) = super::type_sizes(typ).map_err(de::Error::custom)?;
+		let expected_len = sizes.iter().try_fold(elem_size, |len, &size| -> Result<usize, D::Error> {
+			let size = usize::try_from(size)
+				.map_err(|_| de::Error::custom(format!("Mat dimensions must not be negative, but they are: {sizes:?}")))?;
+			len.checked_mul(size)
+				.ok_or_else(|| de::Error::custom(format!("Mat dimensions: {sizes:?} are too large")))
+		})?;
+		if expected_len != data.len() {
+			return Err(de::Error::invalid_length(
+				data.len(),
+				&format!("{expected_len} bytes of Mat data for dimensions: {sizes:?} and type: {typ}").as_str(),
+			));
+		}
+		let mut out = Mat::new_nd_with_default(&sizes, typ, Scalar::all(0.)).map_err(de::Error::custom)?;
+		let out_data = out.data_bytes_mut().map_err(de::Error::custom)?;
+		out_data.copy_from_slice(&data);
+		Ok(out)
+	}
+}
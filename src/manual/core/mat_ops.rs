@@ -6,6 +6,11 @@ use crate::{Error, Result};
 /// Intermediate result type that's produced by the [Mat] operations. Call [MatExprResult::into_result] to get the regular
 /// [Result].
 ///
+/// The `Add`, `Sub`, `Mul` and `Div` operators are implemented for any combination of `Mat`, `MatExpr` (both owned and borrowed)
+/// and `MatExprResult` of those, so the expressions can be chained like `(&a + &b - &c).into_result()?.to_mat()?`. An error
+/// produced by any of the intermediate operations (e.g. because of the mismatched `Mat` sizes) is carried along and surfaces
+/// only when [MatExprResult::into_result] is called.
+///
 /// This type is needed because of Rust orphan rules.
 pub enum MatExprResult<T> {
 	Ok(T),
@@ -32,6 +37,14 @@ impl<T> MatExprResult<T> {
 	}
 }
 
+impl MatExpr {
+	/// Materializes the expression into a typed `Mat_`, fails with `StsUnmatchedFormats` if the result type is not `T`
+	#[inline]
+	pub fn to_mat_typed<T: DataType>(&self) -> Result<Mat_<T>> {
+		self.to_mat()?.try_into_typed()
+	}
+}
+
 /// Elementwise multiplication
 pub trait ElemMul<Rhs = Self> {
 	type Output;
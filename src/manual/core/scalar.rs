@@ -21,6 +21,26 @@ impl<T> Scalar_<T> {
 	}
 }
 
+impl<T: Zero> Scalar_<T> {
+	/// Creates a color value from its blue, green and red components, the order OpenCV uses by default
+	#[inline]
+	pub fn bgr(b: T, g: T, r: T) -> Self {
+		Self::from_array([b, g, r, T::zero()])
+	}
+
+	/// Creates a color value from its red, green and blue components, they are stored in BGR order
+	#[inline]
+	pub fn rgb(r: T, g: T, b: T) -> Self {
+		Self::bgr(b, g, r)
+	}
+
+	/// Creates a single channel grayscale value
+	#[inline]
+	pub fn gray(v: T) -> Self {
+		Self::from_array([v, T::zero(), T::zero(), T::zero()])
+	}
+}
+
 impl<T: Zero> From<T> for Scalar_<T> {
 	#[inline]
 	fn from(v0: T) -> Self {
@@ -0,0 +1,60 @@
+use crate::core::{_InputArray, _OutputArray, Mat, MatTrait, MatTraitConst, Point2f};
+use crate::{core, imgproc, Error, Result};
+
+/// Equalizes the histogram of a color `CV_8UC3` image in BGR order, see [imgproc::equalize_hist()]
+///
+/// The image is converted to the YCrCb color space and only the luma channel is equalized, so the colors are preserved.
+pub fn equalize_hist_color(src: &impl MatTraitConst, dst: &mut impl MatTrait) -> Result<()> {
+	let typ = src.typ();
+	if typ != core::CV_8UC3 {
+		#[cfg(not(ocvrs_opencv_branch_32))]
+		let typ = core::type_to_string(typ)?;
+		return Err(Error::new(
+			core::StsUnsupportedFormat,
+			format!("Mat type must be CV_8UC3 for this operation, but it is: {typ}"),
+		));
+	}
+	let src = _InputArray::from_mat(src)?;
+	let mut ycrcb = Mat::default();
+	imgproc::cvt_color_def(&src, &mut ycrcb, imgproc::COLOR_BGR2YCrCb)?;
+	let mut luma = Mat::default();
+	core::extract_channel(&ycrcb, &mut luma, 0)?;
+	let mut equalized = Mat::default();
+	imgproc::equalize_hist(&luma, &mut equalized)?;
+	core::insert_channel(&equalized, &mut ycrcb, 0)?;
+	let mut dst = _OutputArray::from_mat_mut(dst)?;
+	imgproc::cvt_color_def(&ycrcb, &mut dst, imgproc::COLOR_YCrCb2BGR)
+}
+
+/// Calculates the 2×3 `CV_64F` affine transform that maps the `src` triangle onto the `dst` one, see
+/// [imgproc::get_affine_transform()]
+///
+/// Fails with `StsBadArg` if the points of either triangle are collinear.
+pub fn affine_from_triangles(src: [Point2f; 3], dst: [Point2f; 3]) -> Result<Mat> {
+	fn is_degenerate([a, b, c]: &[Point2f; 3]) -> bool {
+		let (ab_x, ab_y) = (f64::from(b.x - a.x), f64::from(b.y - a.y));
+		let (ac_x, ac_y) = (f64::from(c.x - a.x), f64::from(c.y - a.y));
+		let cross = ab_x * ac_y - ab_y * ac_x;
+		cross.abs() <= f64::from(f32::EPSILON) * ab_x.hypot(ab_y) * ac_x.hypot(ac_y)
+	}
+
+	if is_degenerate(&src) {
+		return Err(Error::new(core::StsBadArg, format!("Source points: {src:?} are collinear")));
+	}
+	if is_degenerate(&dst) {
+		return Err(Error::new(
+			core::StsBadArg,
+			format!("Destination points: {dst:?} are collinear"),
+		));
+	}
+	imgproc::get_affine_transform_slice(&src, &dst)
+}
+
+/// Returns the 2×3 `CV_64F` identity affine transform suitable for [imgproc::warp_affine()]
+///
+/// # Panics
+/// Panics if OpenCV fails to allocate the `Mat`.
+#[inline]
+pub fn affine_identity() -> Mat {
+	Mat::from_slice_2d(&[[1., 0., 0.], [0., 1., 0.]]).expect("Can't allocate the identity affine transform Mat")
+}
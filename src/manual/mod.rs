@@ -2,6 +2,8 @@
 pub mod core;
 #[cfg(ocvrs_has_module_dnn)]
 pub mod dnn;
+#[cfg(ocvrs_has_module_imgproc)]
+pub mod imgproc;
 pub mod sys;
 pub mod types;
 
@@ -10,5 +12,7 @@ pub mod prelude {
 	pub use super::core::MatSizeTraitConstManual;
 	pub use super::core::VectorToVec;
 	#[cfg(ocvrs_has_module_core)]
-	pub use super::core::{MatConstIteratorTraitManual, MatTraitConstManual, MatTraitManual, MatxTrait, ModifyInplace};
+	pub use super::core::{
+		MatConstIteratorTraitManual, MatTraitConstManual, MatTraitManual, MatxTrait, ModifyInplace, UMatTraitConstManual,
+	};
 }
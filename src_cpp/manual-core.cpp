@@ -22,6 +22,45 @@ template<typename T> inline void ocvrs_input_output_array(T* instance, Result<vo
 	} OCVRS_CATCH(ocvrs_return)
 }
 
+namespace {
+	typedef void (*ocvrs_ExternalDataDrop)(void*);
+
+	#if CV_VERSION_MAJOR >= 4
+		typedef cv::AccessFlag ocvrs_AccessFlag;
+	#else
+		typedef int ocvrs_AccessFlag;
+	#endif
+
+	struct ExternalData {
+		ocvrs_ExternalDataDrop drop;
+		void* userdata;
+	};
+
+	// Never allocates, the data is owned by the Rust side and is released by calling back into Rust
+	class ExternalDataAllocator : public cv::MatAllocator {
+	public:
+		cv::UMatData* allocate(int, const int*, int, void*, size_t*, ocvrs_AccessFlag, cv::UMatUsageFlags) const override {
+			return nullptr;
+		}
+
+		bool allocate(cv::UMatData*, ocvrs_AccessFlag, cv::UMatUsageFlags) const override {
+			return false;
+		}
+
+		// Called by the default MatAllocator::unmap() once the last cv::Mat referencing the data is released
+		void deallocate(cv::UMatData* u) const override {
+			if (u) {
+				ExternalData* external = static_cast<ExternalData*>(u->userdata);
+				external->drop(external->userdata);
+				delete external;
+				delete u;
+			}
+		}
+	};
+
+	const ExternalDataAllocator ocvrs_external_data_allocator;
+}
+
 #define ocvrs_ioa(base) \
 	void cv_##base##_input_array(const cv::base* instance, Result<void*>* ocvrs_return) { return ocvrs_input_array(instance, ocvrs_return); } \
 	void cv_##base##_output_array(cv::base* instance, Result<void*>* ocvrs_return) { return ocvrs_output_array(instance, ocvrs_return); } \
@@ -40,6 +79,24 @@ template<typename T> inline void ocvrs_input_output_array(T* instance, Result<vo
 	ocvrs_ioa(base##w)
 
 extern "C" {
+	void cv_manual_Mat_new_rows_cols_with_external_data(int rows, int cols, int type, void* data, ocvrs_ExternalDataDrop drop, void* userdata, Result<void*>* ocvrs_return) {
+		try {
+			std::unique_ptr<cv::Mat> mat(new cv::Mat(rows, cols, type, data));
+			std::unique_ptr<ExternalData> external(new ExternalData{drop, userdata});
+			cv::UMatData* u = new cv::UMatData(&ocvrs_external_data_allocator);
+			u->data = u->origdata = static_cast<uchar*>(data);
+			u->size = mat->total() * mat->elemSize();
+			u->refcount = 1;
+			u->userdata = external.release();
+			mat->u = u;
+			Ok<void*>(mat.release(), ocvrs_return);
+		} OCVRS_CATCH(ocvrs_return)
+	}
+
+	int cv_manual_Mat_refcount(const cv::Mat* instance) {
+		return instance->u ? instance->u->refcount : -1;
+	}
+
 	void cv_InputArray_input_array(cv::_InputArray* instance, Result<void*>* ocvrs_return) { return ocvrs_input_array(instance, ocvrs_return); }
 	void cv_OutputArray_output_array(cv::_OutputArray* instance, Result<void*>* ocvrs_return) { return ocvrs_output_array(instance, ocvrs_return); }
 	void cv_InputOutputArray_input_output_array(cv::_InputOutputArray* instance, Result<void*>* ocvrs_return) { return ocvrs_input_output_array(instance, ocvrs_return); }
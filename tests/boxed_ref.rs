@@ -1,5 +1,5 @@
 use opencv::boxed_ref::{BoxedRef, BoxedRefMut};
-use opencv::core::{no_array, Rect, Vec2i, Vector};
+use opencv::core::{no_array, Rect, Scalar, Vec2i, Vector};
 use opencv::prelude::*;
 use opencv::{core, Result};
 
@@ -47,6 +47,44 @@ fn boxed_ref_clone_pointee() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn boxed_ref_to_owned_mat() -> Result<()> {
+	fn owned_from_slice(data: &[u8]) -> Result<Mat> {
+		Mat::from_slice(data)?.to_owned_mat()
+	}
+
+	let data = [1, 2, 3, 4];
+	let mat = owned_from_slice(&data)?;
+	assert_ne!(data.as_ptr(), mat.data());
+	assert_eq!(&data, mat.data_typed::<u8>()?);
+
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+	let roi: BoxedRefMut<Mat> = mat.roi_mut(Rect::new(1, 0, 2, 2))?;
+	let owned = roi.to_owned_mat()?;
+	assert!(owned.is_continuous());
+	assert_eq!(&[2, 3, 5, 6], owned.data_typed::<i32>()?);
+
+	Ok(())
+}
+
+#[test]
+fn boxed_ref_mut_reborrow() -> Result<()> {
+	fn fill(mut mat: BoxedRefMut<Mat>, val: i32) -> Result<()> {
+		mat.set_to_def(&Scalar::all(f64::from(val)))?;
+		Ok(())
+	}
+
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+	let mut roi = mat.roi_mut(Rect::new(1, 0, 2, 2))?;
+	fill(roi.reborrow()?, 7)?;
+	assert_eq!(7, *roi.at_2d::<i32>(0, 0)?);
+	fill(roi.reborrow()?, 9)?;
+	assert_eq!(9, *roi.at_2d::<i32>(1, 1)?);
+	drop(roi);
+	assert_eq!(&[1, 9, 9, 4, 9, 9], mat.data_typed::<i32>()?);
+	Ok(())
+}
+
 #[test]
 fn boxed_ref_pass() -> Result<()> {
 	let mat = Mat::from_slice(&[1, 2, 3, 4])?.try_clone()?;
@@ -2,9 +2,11 @@
 
 use std::ffi::c_void;
 
+use matches::assert_matches;
+
 use opencv::core::{Point, Point2f, Rect, RotatedRect, Size, Size2f, Vec2f, Vec3b, Vector};
 use opencv::prelude::*;
-use opencv::{imgproc, Result};
+use opencv::{core, imgproc, Error, Result};
 
 #[test]
 fn min_enclosing() -> Result<()> {
@@ -108,3 +110,50 @@ fn box_points() -> Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn equalize_hist_color() -> Result<()> {
+	let src = Mat::from_slice_2d(&[[Vec3b::all(100), Vec3b::all(101)], [Vec3b::all(102), Vec3b::all(103)]])?;
+	let mut dst = Mat::default();
+	imgproc::equalize_hist_color(&src, &mut dst)?;
+	assert_eq!(core::CV_8UC3, dst.typ());
+	assert_eq!(Size::new(2, 2), dst.size()?);
+	assert_eq!(Vec3b::all(0), *dst.at_2d::<Vec3b>(0, 0)?);
+	assert_eq!(Vec3b::all(255), *dst.at_2d::<Vec3b>(1, 1)?);
+
+	let gray = Mat::new_rows_cols_with_default(2, 2, core::CV_8UC1, 0.into())?;
+	assert_matches!(
+		imgproc::equalize_hist_color(&gray, &mut dst),
+		Err(Error {
+			code: core::StsUnsupportedFormat,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn affine_from_triangles() -> Result<()> {
+	let src = [Point2f::new(0., 0.), Point2f::new(1., 0.), Point2f::new(0., 1.)];
+	let dst = [Point2f::new(10., 20.), Point2f::new(12., 20.), Point2f::new(10., 23.)];
+	let transform = imgproc::affine_from_triangles(src, dst)?;
+	assert_eq!(Size::new(3, 2), transform.size()?);
+	let expected = [2., 0., 10., 0., 3., 20.];
+	for (x, expected) in transform.data_typed::<f64>()?.iter().zip(expected) {
+		assert!((x - expected).abs() < 1e-9);
+	}
+
+	let identity = imgproc::affine_identity();
+	assert_eq!(core::CV_64FC1, identity.typ());
+	assert_eq!(&[1., 0., 0., 0., 1., 0.], identity.data_typed::<f64>()?);
+
+	let collinear = [Point2f::new(0., 0.), Point2f::new(1., 1.), Point2f::new(2., 2.)];
+	assert_matches!(
+		imgproc::affine_from_triangles(src, collinear),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
@@ -3,7 +3,10 @@ use std::mem;
 
 use matches::assert_matches;
 
-use opencv::core::{MatConstIterator, MatIter, Point, Point2d, Rect, Scalar, Size, Vec2b, Vec2s, Vec3d, Vec3f, Vec4w, Vector};
+use opencv::core::{
+	MatBuilder, MatConstIterator, MatCow, MatDepth, MatElementType, MatIter, MatPool, Point, Point2d, Rect, Scalar, Size, Vec2b,
+	Vec2s, Vec3b, Vec3d, Vec3f, Vec3w, Vec4b, Vec4w, Vector,
+};
 use opencv::prelude::*;
 use opencv::{core, imgproc, Error, Result};
 const PIXEL: &[u8] = include_bytes!("pixel.png");
@@ -323,6 +326,25 @@ fn mat_at_2d() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn mat_get_2d() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+	assert_eq!(Some(&2), mat.get_2d::<i32>(0, 1));
+	assert_eq!(Some(&6), mat.get_2d::<i32>(1, 2));
+	assert_eq!(None, mat.get_2d::<i32>(2, 0));
+	assert_eq!(None, mat.get_2d::<i32>(0, 3));
+	assert_eq!(None, mat.get_2d::<i32>(-1, 0));
+	assert_eq!(None, mat.get_2d::<f32>(0, 0));
+	*mat.get_2d_mut::<i32>(1, 0).expect("In bounds") = 40;
+	assert_eq!(40, *mat.at_2d::<i32>(1, 0)?);
+	assert!(mat.get_2d_mut::<i32>(1, 3).is_none());
+	assert!(mat.get_2d_mut::<u8>(0, 0).is_none());
+
+	let mat = Mat::new_nd_with_default(&[2, 2, 2], i32::opencv_type(), 0.into())?;
+	assert_eq!(None, mat.get_2d::<i32>(0, 0));
+	Ok(())
+}
+
 #[test]
 fn mat_at_2d_multichannel() -> Result<()> {
 	let mut mat = Mat::new_rows_cols_with_default(100, 100, Vec3f::opencv_type(), Scalar::all(1.23))?;
@@ -411,6 +433,57 @@ fn mat_at_row() -> Result<()> {
 	Ok(())
 }
 
+#[test]
+fn mat_at_rows() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6], [7, 8, 9], [10, 11, 12]])?;
+	assert_eq!(&[4, 5, 6, 7, 8, 9], mat.at_rows::<i32>(1, 3)?);
+	assert_eq!(12, mat.at_rows::<i32>(0, 4)?.len());
+	assert!(mat.at_rows::<i32>(2, 2)?.is_empty());
+	assert!(mat.at_rows::<i32>(4, 4)?.is_empty());
+	assert_matches!(
+		mat.at_rows::<i32>(5, 5),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.at_rows::<i32>(2, 5),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.at_rows::<i32>(2, 1),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.at_rows::<u8>(0, 1),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+
+	let no_cols = Mat::new_rows_cols_with_default(3, 0, i32::opencv_type(), 0.into())?;
+	assert!(no_cols.at_rows::<i32>(0, 3)?.is_empty());
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 3))?;
+	assert_eq!(&[5, 6], roi.at_rows::<i32>(0, 1)?);
+	assert_matches!(
+		roi.at_rows::<i32>(0, 2),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
 #[test]
 fn mat_at_pt() -> Result<()> {
 	let s: Vec<Vec<f32>> = vec![vec![1., 2., 3.], vec![4., 5., 6.], vec![7., 8., 9.]];
@@ -466,72 +539,36 @@ fn mat_vec() -> Result<()> {
 }
 
 #[test]
-fn mat_continuous() -> Result<()> {
-	let s: Vec<Vec<f32>> = vec![vec![1., 2., 3.], vec![4., 5., 6.], vec![7., 8., 9.]];
-
-	let mut mat = Mat::from_slice_2d(&s)?;
-
+fn mat_rows_typed() -> Result<()> {
 	{
-		let sub_mat_non_cont = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
-		assert_eq!(mat.typ(), sub_mat_non_cont.typ());
-		assert_eq!(2, sub_mat_non_cont.rows());
-		assert_eq!(2, sub_mat_non_cont.cols());
-		assert!(sub_mat_non_cont.is_submatrix());
-		assert!(!sub_mat_non_cont.is_continuous());
-		assert_eq!(5., *sub_mat_non_cont.at_2d::<f32>(0, 0)?);
-		assert_eq!(6., *sub_mat_non_cont.at_2d::<f32>(0, 1)?);
-		assert_eq!(8., *sub_mat_non_cont.at_2d::<f32>(1, 0)?);
-		assert_eq!(9., *sub_mat_non_cont.at_2d::<f32>(1, 1)?);
-
-		let vec = sub_mat_non_cont.to_vec_2d::<f32>()?;
-		assert_eq!(5., vec[0][0]);
-		assert_eq!(6., vec[0][1]);
-		assert_eq!(8., vec[1][0]);
-		assert_eq!(9., vec[1][1]);
-
-		let mat_clone = sub_mat_non_cont.try_clone()?;
-		assert_eq!(mat.typ(), mat_clone.typ());
-		assert_eq!(2, mat_clone.rows());
-		assert_eq!(2, mat_clone.cols());
-		assert!(!mat_clone.is_submatrix());
-		assert!(mat_clone.is_continuous());
-		assert_eq!(5., *mat_clone.at_2d::<f32>(0, 0)?);
-		assert_eq!(6., *mat_clone.at_2d::<f32>(0, 1)?);
-		assert_eq!(8., *mat_clone.at_2d::<f32>(1, 0)?);
-		assert_eq!(9., *mat_clone.at_2d::<f32>(1, 1)?);
+		let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+		let mut rows = mat.rows_typed::<i32>()?;
+		assert_eq!(2, rows.len());
+		assert_eq!(Some([1, 2, 3].as_slice()), rows.next());
+		assert_eq!(Some([4, 5, 6].as_slice()), rows.next());
+		assert_eq!(None, rows.next());
 	}
 
 	{
-		let sub_mat_cont = Mat::roi(&mat, Rect::new(0, 1, 3, 2))?;
-		assert_eq!(mat.typ(), sub_mat_cont.typ());
-		assert_eq!(2, sub_mat_cont.rows());
-		assert_eq!(3, sub_mat_cont.cols());
-		assert!(sub_mat_cont.is_submatrix());
-		assert!(sub_mat_cont.is_continuous());
-		assert_eq!(4., *sub_mat_cont.at_2d::<f32>(0, 0)?);
-		assert_eq!(6., *sub_mat_cont.at_2d::<f32>(0, 2)?);
-		assert_eq!(7., *sub_mat_cont.at_2d::<f32>(1, 0)?);
-		assert_eq!(9., *sub_mat_cont.at_2d::<f32>(1, 2)?);
-
-		let vec = sub_mat_cont.to_vec_2d::<f32>()?;
-		assert_eq!(4., vec[0][0]);
-		assert_eq!(6., vec[0][2]);
-		assert_eq!(7., vec[1][0]);
-		assert_eq!(9., vec[1][2]);
+		let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		assert!(!roi.is_continuous());
+		let rows = roi.rows_typed::<i32>()?.collect::<Vec<_>>();
+		assert_eq!(vec![[6, 7].as_slice(), [10, 11].as_slice()], rows);
 	}
 
 	{
-		let mut sub_mat_non_cont = Mat::roi_mut(&mut mat, Rect::new(1, 1, 1, 2))?;
-		assert!(!sub_mat_non_cont.is_continuous());
+		let mat = Mat::from_slice_2d(&[[1u8, 2], [3, 4]])?;
 		assert_matches!(
-			sub_mat_non_cont.data_typed::<f32>(),
+			mat.rows_typed::<i32>(),
 			Err(Error {
-				code: core::StsUnmatchedSizes,
+				code: core::StsUnmatchedFormats,
 				..
 			})
 		);
+		let mat = Mat::new_nd_with_default(&[2, 2, 2], u8::opencv_type(), 0.into())?;
 		assert_matches!(
-			sub_mat_non_cont.data_typed_mut::<f32>(),
+			mat.rows_typed::<u8>(),
 			Err(Error {
 				code: core::StsUnmatchedSizes,
 				..
@@ -543,542 +580,505 @@ fn mat_continuous() -> Result<()> {
 }
 
 #[test]
-fn mat_merge_split() -> Result<()> {
-	let mut src = Vector::<Mat>::new();
-	src.push(Mat::new_rows_cols_with_default(1, 3, u8::opencv_type(), 1.into())?);
-	src.push(Mat::new_rows_cols_with_default(1, 3, u8::opencv_type(), 2.into())?);
-	let mut merged = Mat::default();
-	core::merge(&src, &mut merged)?;
-	assert_eq!(merged.typ(), Vec2b::opencv_type());
-	assert_eq!(merged.at_2d::<Vec2b>(0, 1)?[0], 1);
-	assert_eq!(merged.at_2d::<Vec2b>(0, 2)?[1], 2);
-
-	let mut split = Vector::<Mat>::new();
-	core::split(&merged, &mut split)?;
-	assert_eq!(2, split.len());
-	let mat = split.get(0)?;
-	assert_eq!(u8::opencv_type(), mat.typ());
-	assert_eq!(1, mat.channels());
-	assert_eq!(1, *mat.at_2d::<u8>(0, 2)?);
-	let mat = split.get(1)?;
-	assert_eq!(u8::opencv_type(), mat.typ());
-	assert_eq!(1, mat.channels());
-	assert_eq!(2, *mat.at_2d::<u8>(0, 0)?);
-
-	Ok(())
-}
-
-#[test]
-fn mat_from_data() -> Result<()> {
-	let mut bytes = PIXEL.to_vec();
-	assert_eq!(90, bytes.len());
-
-	{
-		let src = unsafe {
-			Mat::new_rows_cols_with_data_unsafe_def(
-				1,
-				PIXEL.len().try_into()?,
-				u8::opencv_type(),
-				bytes.as_mut_ptr().cast::<c_void>(),
-			)?
-		};
-		assert_eq!(Size::new(PIXEL.len().try_into()?, 1), src.size()?);
-		assert_eq!(PIXEL.len(), src.total());
-		let row = src.at_row::<u8>(0)?;
-		assert_eq!(0x89, row[0]);
-		assert_eq!(0x50, row[1]);
-		assert_eq!(0x1A, row[6]);
-		assert_eq!(0x0D, row[11]);
-		assert_eq!(0x82, row[89]);
-	}
-
-	{
-		let src = unsafe { Mat::new_nd_with_data_unsafe_def(&[3, 5, 6], u8::opencv_type(), bytes.as_mut_ptr().cast::<c_void>())? };
-		assert_eq!(Size::new(5, 3), src.size()?);
-		assert_eq!(PIXEL.len(), src.total());
-		assert_eq!(0x89, *src.at_3d::<u8>(0, 0, 0)?);
-		assert_eq!(0x50, *src.at_3d::<u8>(0, 0, 1)?);
-		assert_eq!(0x1A, *src.at_3d::<u8>(0, 1, 0)?);
-		assert_eq!(0x0D, *src.at_3d::<u8>(0, 1, 5)?);
-		assert_eq!(0x82, *src.at_3d::<u8>(2, 4, 5)?);
-	}
-
+fn mat_copy_to_slice() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6], [7, 8, 9]])?;
 	{
-		let mut bytes = bytes.clone();
-		let mut mat = unsafe {
-			Mat::new_rows_cols_with_data_unsafe_def(
-				1,
-				bytes.len().try_into()?,
-				u8::opencv_type(),
-				bytes.as_mut_ptr().cast::<c_void>(),
-			)?
-		};
-		assert_eq!(mat.data(), bytes.as_ptr());
-		bytes[0] = 0xFF;
-		assert_eq!(0xFF, *mat.at::<u8>(0)?);
-		mat.resize_with_default(100, 0.into())?;
-		assert_ne!(mat.data(), bytes.as_ptr());
-		bytes[0] = 0xAA;
-		let row = mat.at_row::<u8>(0)?;
-		assert_eq!(0xFF, row[0]);
-		assert_eq!(0x50, row[1]);
-		assert_eq!(0x1A, row[6]);
-		assert_eq!(0x0D, row[11]);
-		assert_eq!(0x82, row[89]);
-		let row = mat.at_row::<u8>(1)?;
-		assert_eq!(0, row[1]);
-		assert_eq!(0, row[6]);
-		assert_eq!(0, row[89]);
+		let mut dst = [0; 9];
+		mat.copy_to_slice(&mut dst)?;
+		assert_eq!([1, 2, 3, 4, 5, 6, 7, 8, 9], dst);
 	}
-	Ok(())
-}
 
-#[test]
-fn mat_from_matexpr() -> Result<()> {
 	{
-		let mat = Mat::zeros(3, 4, f32::opencv_type())?.to_mat()?;
-		assert_eq!(4, mat.cols());
-		assert_eq!(3, mat.rows());
-		assert_eq!(0., *mat.at_2d::<f32>(0, 0)?);
-		assert_eq!(0., *mat.at_2d::<f32>(1, 1)?);
-		assert_eq!(0., *mat.at_2d::<f32>(2, 3)?);
+		let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 3))?;
+		let mut dst = [0; 6];
+		roi.copy_to_slice(&mut dst)?;
+		assert_eq!([2, 3, 5, 6, 8, 9], dst);
 	}
 
 	{
-		let mat = Mat::ones_nd(&[6, 5], f32::opencv_type())?.to_mat()?;
-		assert_eq!(5, mat.cols());
-		assert_eq!(6, mat.rows());
-		assert_eq!(1., *mat.at_2d::<f32>(0, 0)?);
-		assert_eq!(1., *mat.at_2d::<f32>(1, 1)?);
-		assert_eq!(1., *mat.at_2d::<f32>(5, 4)?);
+		let roi = Mat::roi(&mat, Rect::new(1, 0, 0, 2))?;
+		let mut dst: [i32; 0] = [];
+		roi.copy_to_slice(&mut dst)?;
+		Mat::default().copy_to_slice::<u8>(&mut [])?;
 	}
-	Ok(())
-}
 
-#[test]
-fn mat_const_iterator() -> Result<()> {
 	{
-		let mat = Mat::from_slice(&[1, 2, 3, 4])?;
-		let mut pos = [0, 0];
-		let mut iter = MatConstIterator::over(&mat)?;
-		unsafe {
-			iter.pos_to(pos.as_mut_ptr())?;
-		}
-		assert_eq!(iter.typ(), mat.typ());
-		assert_eq!(1, *iter.current::<i32>()?);
-		assert_eq!(Point::new(0, 0), iter.pos()?);
-		assert_eq!([0, 0], pos);
-		assert!(iter.has_elements());
-		iter.seek(1, true)?;
-		unsafe {
-			iter.pos_to(pos.as_mut_ptr())?;
-		}
-		assert_eq!(2, *iter.current::<i32>()?);
-		assert_eq!(Point::new(1, 0), iter.pos()?);
-		assert_eq!([0, 1], pos);
-		assert!(iter.has_elements());
-		iter.seek(1, true)?;
-		unsafe {
-			iter.pos_to(pos.as_mut_ptr())?;
-		}
-		assert_eq!(3, *iter.current::<i32>()?);
-		assert_eq!(Point::new(2, 0), iter.pos()?);
-		assert_eq!([0, 2], pos);
-		assert!(iter.has_elements());
-		iter.seek(1, true)?;
-		unsafe {
-			iter.pos_to(pos.as_mut_ptr())?;
-		}
-		assert_eq!(4, *iter.current::<i32>()?);
-		assert_eq!(Point::new(3, 0), iter.pos()?);
-		assert_eq!([0, 3], pos);
-		assert!(iter.has_elements());
-		iter.seek(1, true)?;
-		unsafe {
-			iter.pos_to(pos.as_mut_ptr())?;
-		}
+		let mut dst = [0; 8];
 		assert_matches!(
-			iter.current::<i32>(),
+			mat.copy_to_slice(&mut dst),
 			Err(Error {
-				code: core::StsOutOfRange,
+				code: core::StsUnmatchedSizes,
 				..
 			})
 		);
-		assert_eq!(Point::new(0, 1), iter.pos()?);
-		assert_eq!([1, 0], pos);
-		assert!(!iter.has_elements());
-		iter.seek(1, true)?;
-		unsafe {
-			iter.pos_to(pos.as_mut_ptr())?;
-		}
+		let mut dst = [0u8; 9];
 		assert_matches!(
-			iter.current::<i32>(),
+			mat.copy_to_slice(&mut dst),
 			Err(Error {
-				code: core::StsOutOfRange,
+				code: core::StsUnmatchedFormats,
 				..
 			})
 		);
-		assert_eq!(Point::new(0, 1), iter.pos()?);
-		assert_eq!([1, 0], pos);
-		assert!(!iter.has_elements());
 	}
-
 	Ok(())
 }
 
 #[test]
-fn mat_iterator() -> Result<()> {
+fn mat_vec_3d() -> Result<()> {
 	{
-		let mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
-		for (pos, x) in mat.iter::<i32>()? {
-			match pos {
-				Point { x: 0, y: 0 } => assert_eq!(x, 1),
-				Point { x: 1, y: 0 } => assert_eq!(x, 2),
-				Point { x: 0, y: 1 } => assert_eq!(x, 3),
-				Point { x: 1, y: 1 } => assert_eq!(x, 4),
-				_ => panic!("Too many elements"),
-			}
-		}
-		for (pos, x) in MatIter::<i32>::new(MatConstIterator::with_start(&mat, Point::new(1, 0))?)? {
-			match pos {
-				Point { x: 1, y: 0 } => assert_eq!(x, 2),
-				Point { x: 0, y: 1 } => assert_eq!(x, 3),
-				Point { x: 1, y: 1 } => assert_eq!(x, 4),
-				_ => panic!("Too many elements"),
-			}
-		}
-	}
-
-	{
-		let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
-		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
-		for (pos, x) in roi.iter::<i32>()? {
-			match pos {
-				Point { x: 0, y: 0 } => assert_eq!(x, 6),
-				Point { x: 1, y: 0 } => assert_eq!(x, 7),
-				Point { x: 0, y: 1 } => assert_eq!(x, 10),
-				Point { x: 1, y: 1 } => assert_eq!(x, 11),
-				_ => panic!("Too many elements"),
-			}
-		}
-	}
-
-	{
-		let mut mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
-		for (pos, x) in mat.iter_mut::<i32>()? {
-			*x *= pos.x + pos.y;
-		}
-		assert_eq!([0, 2, 3, 8], mat.data_typed::<i32>()?);
+		let src = [1u16, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+		let mat = Mat::new_nd_with_data(&[2, 3, 2], &src)?;
+		let v = mat.to_vec_3d::<u16>()?;
+		assert_eq!(
+			vec![
+				vec![vec![1, 2], vec![3, 4], vec![5, 6]],
+				vec![vec![7, 8], vec![9, 10], vec![11, 12]]
+			],
+			v
+		);
 	}
 
 	{
-		let mut mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
-		let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 1, 2, 2))?;
-		for (pos, x) in roi.iter_mut::<i32>()? {
-			*x += pos.x + pos.y;
-		}
+		let src = [0i32; 27];
+		let mut mat = Mat::new_nd_with_data(&[3, 3, 3], &src)?.try_clone()?;
+		*mat.at_3d_mut::<i32>(1, 2, 0)? = 5;
+		let mut ranges = Vector::<core::Range>::new();
+		ranges.push(core::Range::new(1, 3)?);
+		ranges.push(core::Range::new(1, 3)?);
+		ranges.push(core::Range::new(0, 2)?);
+		let sub = mat.ranges(&ranges)?;
+		assert!(!sub.is_continuous());
 		assert_eq!(
-			[1, 2, 3, 4, 5, 6, 8, 8, 9, 11, 13, 12, 13, 14, 15, 16],
-			mat.data_typed::<i32>()?
+			vec![vec![vec![0, 0], vec![5, 0]], vec![vec![0, 0], vec![0, 0]]],
+			sub.to_vec_3d::<i32>()?
 		);
 	}
 
 	{
-		let mat = Mat::from_slice::<u8>(&[])?;
-		#[allow(clippy::never_loop)]
-		for _ in mat.iter::<u8>()? {
-			panic!("Mat must be empty");
-		}
+		let mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
+		assert_matches!(
+			mat.to_vec_3d::<i32>(),
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
 	}
 	Ok(())
 }
 
 #[test]
-fn mat_locate_roi() -> Result<()> {
-	let mat = Mat::from_slice(&[1, 2, 3, 4])?;
-	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 1))?;
-	let mut sz = Size::default();
-	let mut ofs = Point::default();
-	roi.locate_roi(&mut sz, &mut ofs)?;
-	assert_eq!(sz, Size::new(4, 1));
-	assert_eq!(ofs, Point::new(1, 0));
+fn mat_elem_ptr() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1u16, 2, 3], [4, 5, 6]])?;
+	let ptr = mat.elem_ptr::<u16>(1, 2)?;
+	assert_eq!(6, unsafe { *ptr });
+	assert_eq!(mat.at_2d::<u16>(1, 2)? as *const u16, ptr);
+
+	let ptr = mat.elem_ptr_mut::<u16>(0, 1)?;
+	unsafe { *ptr = 20 };
+	assert_eq!(20, *mat.at_2d::<u16>(0, 1)?);
+
+	assert_matches!(
+		mat.elem_ptr::<u16>(2, 0),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.elem_ptr_mut::<u8>(0, 0),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
 	Ok(())
 }
 
 #[test]
-fn mat_roi() -> Result<()> {
-	let mut mat = Mat::from_slice(&[1, 2, 3, 4])?.try_clone()?;
-	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 1))?;
-	assert_eq!(2, *roi.at(0)?);
-	assert_eq!(3, *roi.at(1)?);
-
-	let roi = mat.roi(Rect::new(1, 0, 2, 1))?;
-	assert_eq!(2, *roi.at(0)?);
-	assert_eq!(3, *roi.at(1)?);
-
-	let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 0, 2, 1))?;
-	assert_eq!(2, *roi.at(0)?);
-	assert_eq!(3, *roi.at(1)?);
-	*roi.at_mut(0)? = 10;
-	*roi.at_mut(1)? = 11;
-	assert_eq!(&[1, 10, 11, 4], mat.data_typed::<i32>()?);
+fn mat_elem_ptr_nd() -> Result<()> {
+	let mut mat = Mat::from_slice_nd(&[2, 2, 3], &[1i32, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12])?;
+	let ptr = mat.elem_ptr_nd::<i32>(&[1, 0, 2])?;
+	assert_eq!(9, unsafe { *ptr });
+	assert_eq!(mat.at_nd::<i32>(&[1, 0, 2])? as *const i32, ptr);
 
-	let mut roi = mat.roi_mut(Rect::new(1, 0, 2, 1))?;
-	assert_eq!(10, *roi.at(0)?);
-	assert_eq!(11, *roi.at(1)?);
-	*roi.at_mut(0)? = 20;
-	*roi.at_mut(1)? = 21;
-	assert_eq!(&[1, 20, 21, 4], mat.data_typed::<i32>()?);
+	let ptr = mat.elem_ptr_nd_mut::<i32>(&[0, 1, 1])?;
+	unsafe { *ptr = 50 };
+	assert_eq!(50, *mat.at_nd::<i32>(&[0, 1, 1])?);
 
+	assert_matches!(
+		mat.elem_ptr_nd::<i32>(&[1, 2, 0]),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.elem_ptr_nd::<i32>(&[1, 1]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		mat.elem_ptr_nd_mut::<f32>(&[0, 0, 0]),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
 	Ok(())
 }
 
 #[test]
-fn mat_roi_2() -> Result<()> {
-	let mut mat = Mat::from_slice(&[1, 2, 3, 4])?.try_clone()?;
-	let (mut roi1, mut roi2) = Mat::roi_2_mut(&mut mat, Rect::new(0, 0, 2, 1), Rect::new(2, 0, 2, 1))?;
-	assert_eq!(1, *roi1.at(0)?);
-	assert_eq!(2, *roi1.at(1)?);
-	assert_eq!(3, *roi2.at(0)?);
-	assert_eq!(4, *roi2.at(1)?);
+fn mat_channel_view() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[
+		[Vec3b::from([1, 2, 3]), Vec3b::from([4, 5, 6])],
+		[Vec3b::from([7, 8, 9]), Vec3b::from([10, 11, 12])],
+	])?;
+	assert_eq!(vec![1, 4, 7, 10], mat.channel_view::<u8>(0)?.copied().collect::<Vec<_>>());
+	assert_eq!(vec![3, 6, 9, 12], mat.channel_view::<u8>(2)?.copied().collect::<Vec<_>>());
 
-	mem::swap(roi1.at_mut::<i32>(0)?, roi2.at_mut::<i32>(0)?);
-	mem::swap(roi1.at_mut::<i32>(1)?, roi2.at_mut::<i32>(1)?);
-	assert_eq!(3, *mat.at(0)?);
-	assert_eq!(4, *mat.at(1)?);
-	assert_eq!(1, *mat.at(2)?);
-	assert_eq!(2, *mat.at(3)?);
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 1, 2))?;
+	assert_eq!(vec![5, 11], roi.channel_view::<u8>(1)?.copied().collect::<Vec<_>>());
 
 	assert_matches!(
-		Mat::roi_2_mut(&mut mat, Rect::new(0, 0, 3, 1), Rect::new(2, 0, 2, 1)),
+		mat.channel_view::<u8>(3),
 		Err(Error {
-			code: core::StsBadArg,
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.channel_view::<Vec3b>(0),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
 			..
 		})
 	);
-
 	Ok(())
 }
 
 #[test]
-fn mat_convert() -> Result<()> {
-	let mat = Mat::from_slice(&[1, 2, 3, 4])?;
-	let mut mat_ = mat.try_clone()?.try_into_typed::<i32>()?;
-	assert_eq!(3, *mat_.at(2)?);
-	*mat_.at_mut(3)? = 8;
-	assert_eq!(8, *mat_.at(3)?);
-	assert_eq!(mat.typ(), mat_.typ());
-	assert_eq!(mat.size()?, mat_.size()?);
-	let mat_back = mat_.into_untyped();
-	assert_eq!(mat.size()?, mat_back.size()?);
+fn mat_from_vec() -> Result<()> {
+	let data = vec![1.5f32, 2.5, 3.5, 4.5, 5.5, 6.5];
+	let data_ptr = data.as_ptr();
+	let mat = Mat::from_vec(data, 2, 3)?;
+	assert_eq!(f32::opencv_type(), mat.typ());
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert_eq!(data_ptr.cast(), mat.data());
+	assert_eq!(6.5, *mat.at_2d::<f32>(1, 2)?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	assert_eq!(vec![vec![2.5, 3.5], vec![5.5, 6.5]], roi.to_vec_2d::<f32>()?);
+
+	assert_matches!(
+		Mat::from_vec(vec![1u8, 2, 3], 2, 2),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
 	Ok(())
 }
 
 #[test]
-fn mat_mul() -> Result<()> {
-	{
-		let expr = Mat::ones(1, 5, f64::opencv_type())?;
-		let res = core::mul_matexpr_f64(&expr, 4.)?.to_mat()?;
-		assert_eq!(res.typ(), f64::opencv_type());
-		assert_eq!(res.data_typed::<f64>()?, &[4., 4., 4., 4., 4.]);
-	}
-	{
-		let expr = Mat::new_rows_cols_with_default(2, 3, i32::opencv_type(), 9.into())?;
-		let res = core::sub_mat_scalar(&expr, 5.into())?.to_mat()?;
-		assert_eq!(res.typ(), i32::opencv_type());
-		assert_eq!(res.data_typed::<i32>()?, &[4, 4, 4, 4, 4, 4]);
-	}
-	{
-		let mat1 = Mat::from_slice_2d(&[[1f32, 2., 3.], [4., 5., 6.]])?;
-		let mat2 = Mat::from_slice_2d(&[[7f32, 8.], [9., 10.], [11., 12.]])?;
-		let res = core::mul_mat_mat(&mat1, &mat2)?.to_mat()?;
-		assert_eq!(res.typ(), f32::opencv_type());
-		assert_eq!(res.size()?, Size::new(2, 2));
-		assert_eq!(res.data_typed::<f32>()?, &[58., 64., 139., 154.]);
-	}
-	{
-		let mat = Mat::from_slice(&[1u8, 2, 3, 4, 5, 6])?;
-		let res = core::div_mat_f64(&mat, 2.)?.to_mat()?;
-		assert_eq!(res.typ(), u8::opencv_type());
-		assert_eq!(res.data_typed::<u8>()?, &[0, 1, 2, 2, 2, 3]);
-	}
+fn mat_view_as() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1f32, 2., 3.], [4., 5., 6.]])?;
+	let view = mat.view_as::<Vec4b>()?;
+	assert_eq!(Vec4b::opencv_type(), view.typ());
+	assert_eq!(mat.size()?, view.size()?);
+	assert_eq!(mat.data(), view.data());
+	assert_eq!(Vec4b::from(4f32.to_ne_bytes()), *view.at_2d::<Vec4b>(1, 0)?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	let view = roi.view_as::<i32>()?;
+	assert!(!view.is_continuous());
+	assert_eq!(6f32.to_bits(), *view.at_2d::<i32>(1, 1)? as u32);
+
+	let no_data = Mat::new_rows_cols_with_default(5, 0, f32::opencv_type(), Scalar::all(0.))?;
+	let view = no_data.view_as::<i32>()?;
+	assert_eq!(i32::opencv_type(), view.typ());
+	assert_eq!(Size::new(0, 5), view.size()?);
+
+	assert_matches!(
+		mat.view_as::<f64>(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
 	Ok(())
 }
 
 #[test]
-fn mat_data() -> Result<()> {
-	{
-		let mat = Mat::from_slice(&[8, 13, 21, 39u8])?;
-		assert_eq!(&[8, 13, 21, 39], mat.data_bytes()?);
-		let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 1))?;
-		assert_eq!(&[13, 21], roi.data_bytes()?);
-	}
-
+fn mat_ref_count() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(3, 3, u8::opencv_type(), Scalar::all(0.))?;
+	assert_eq!(Some(1), mat.ref_count());
 	{
-		let mat = Mat::from_slice_2d(&[[6, 7], [16, 17u8]])?;
-		assert_eq!(&[6, 7, 16, 17], mat.data_bytes()?);
-		let roi = Mat::roi(&mat, Rect::new(0, 0, 1, 2))?;
-		assert_matches!(
-			roi.data_bytes(),
-			Err(Error {
-				code: core::StsUnmatchedSizes,
-				..
-			})
-		);
+		let roi = mat.adjust_roi(0, 0, 0, -1)?;
+		assert_eq!(Some(2), mat.ref_count());
+		assert_eq!(Some(2), roi.ref_count());
 	}
+	assert_eq!(Some(1), mat.ref_count());
+	assert_eq!(Some(1), mat.try_clone()?.ref_count());
 
-	{
-		let mut mat = Mat::from_slice(&[Vec2b::from([5, 8]), Vec2b::from([13, 21])])?.try_clone()?;
-		assert_eq!(&[5, 8, 13, 21], mat.data_bytes()?);
-		let bytes = mat.data_bytes_mut()?;
-		bytes[1] = 90;
-		assert_eq!(&[5, 90, 13, 21], mat.data_bytes()?);
-	}
+	let data = [1u8, 2, 3];
+	assert_eq!(None, Mat::from_slice(&data)?.ref_count());
+	assert_eq!(None, Mat::default().ref_count());
 	Ok(())
 }
 
 #[test]
-fn mat_equals() -> Result<()> {
-	let mat1 = Mat::new_rows_cols_with_default(3, 3, i32::opencv_type(), 0.into())?;
-	let mat2 = Mat::new_rows_cols_with_default(3, 3, i32::opencv_type(), 0.into())?;
-	let mat3 = Mat::new_rows_cols_with_default(3, 3, i32::opencv_type(), Scalar::all(1.))?;
-	let res = core::equals_mat_mat(&mat1, &mat2)?.to_mat()?;
-	assert!(res.data_typed::<u8>()?.iter().all(|&e| e != 0));
-	let res = core::equals_mat_mat(&mat1, &mat3)?.to_mat()?;
-	assert!(res.data_typed::<u8>()?.iter().all(|&e| e == 0));
+fn mat_clone_continuous() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u16, 2, 3], [4, 5, 6], [7, 8, 9]])?;
+	let cont = mat.clone_continuous()?;
+	assert!(cont.is_continuous());
+	assert_eq!(mat.data_typed::<u16>()?, cont.data_typed::<u16>()?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+	assert!(!roi.is_continuous());
+	let cont = roi.clone_continuous()?;
+	assert!(cont.is_continuous());
+	assert_eq!(roi.typ(), cont.typ());
+	assert_eq!(roi.size()?, cont.size()?);
+	assert_eq!(&[5, 6, 8, 9], cont.data_typed::<u16>()?);
 	Ok(())
 }
 
 #[test]
-fn mat_rgb() -> Result<()> {
-	#![cfg(feature = "rgb")]
-	let m = Mat::new_rows_cols_with_default(3, 3, rgb::RGBA8::opencv_type(), Scalar::all(1.))?;
-	assert_eq!(rgb::RGBA::new(1, 1, 1, 1), *m.at_2d(1, 1)?);
+fn mat_reshape_checked() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(4, 6, Vec3b::opencv_type(), Scalar::all(1.))?;
+	let reshaped = mat.reshape_checked(1, &[2, 36])?;
+	assert_eq!(u8::opencv_type(), reshaped.typ());
+	assert_eq!([2, 36], *reshaped.mat_size());
+
+	let reshaped = mat.reshape_checked(0, &[2, 3, 4])?;
+	assert_eq!(Vec3b::opencv_type(), reshaped.typ());
+	assert_eq!([2, 3, 4], *reshaped.mat_size());
+
 	assert_matches!(
-		m.at_2d::<rgb::RGB8>(1, 1),
+		mat.reshape_checked(1, &[5, 5]),
 		Err(Error {
-			code: core::StsUnmatchedFormats,
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		mat.reshape_checked(1, &[-2, -36]),
+		Err(Error {
+			code: core::StsOutOfRange,
 			..
 		})
 	);
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+	let res = roi.reshape_checked(1, &[12]);
+	assert_matches!(
+		res,
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert!(res.unwrap_err().message.contains("is_continuous"));
 	Ok(())
 }
 
 #[test]
-fn mat_from_slice() -> Result<()> {
-	let src_u8 = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
-	let src_point = [
-		Point2d::new(10.1, 20.2),
-		Point2d::new(30.3, 40.4),
-		Point2d::new(50.5, 60.6),
-		Point2d::new(70.7, 80.8),
-	];
+fn mat_slice_rows_cols() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6], [7, 8, 9]])?;
+	assert_eq!(vec![vec![4, 5, 6], vec![7, 8, 9]], mat.slice_rows(1..)?.to_vec_2d::<i32>()?);
+	assert_eq!(vec![vec![1, 2, 3]], mat.slice_rows(..1)?.to_vec_2d::<i32>()?);
+	assert_eq!(3, mat.slice_rows(..)?.rows());
+	assert_eq!(
+		vec![vec![2, 3], vec![5, 6], vec![8, 9]],
+		mat.slice_cols(1..=2)?.to_vec_2d::<i32>()?
+	);
+	assert_eq!(0, mat.slice_cols(2..2)?.cols());
 
-	{
-		let mat = Mat::from_slice(&src_u8)?;
-		assert_eq!(10, mat.total());
-		assert_eq!(5u8, *mat.at(5)?);
-
-		let mat = Mat::from_slice(&src_point)?;
-		assert_eq!(4, mat.total());
-		assert_eq!(Point2d::new(30.3, 40.4), *mat.at(1)?);
-	}
+	assert_matches!(
+		mat.slice_rows(2..4),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.slice_cols(..=3),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
 
-	{
-		let mat = Mat::new_rows_cols_with_data(2, 5, &src_u8)?;
-		assert_eq!(10, mat.total());
-		assert_eq!(2, mat.rows());
-		assert_eq!(5, mat.cols());
-		assert_eq!(6u8, *mat.at_2d(1, 1)?);
+#[test]
+fn mat_row_col_views() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+	let rows = mat.row_views()?;
+	assert_eq!(2, rows.len());
+	let rows = rows.map(|row| row.to_vec_2d::<i32>()).collect::<Result<Vec<_>>>()?;
+	assert_eq!(vec![vec![vec![1, 2, 3]], vec![vec![4, 5, 6]]], rows);
+
+	let cols = mat.col_views()?;
+	assert_eq!(3, cols.len());
+	let cols = cols.map(|col| col.to_vec_2d::<i32>()).collect::<Result<Vec<_>>>()?;
+	assert_eq!(
+		vec![vec![vec![1], vec![4]], vec![vec![2], vec![5]], vec![vec![3], vec![6]]],
+		cols
+	);
 
-		let mat_sz = Mat::new_size_with_data(Size::new(5, 2), &src_u8)?;
-		assert_eq!(mat.data_typed::<u8>()?, mat_sz.data_typed::<u8>()?);
+	assert_eq!(0, Mat::default().row_views()?.count());
+	assert_eq!(0, Mat::default().col_views()?.count());
+	Ok(())
+}
 
-		let mat_nd = Mat::new_nd_with_data(&[2, 5], &src_u8)?;
-		assert_eq!(mat.rows(), mat_nd.rows());
-		assert_eq!(mat.cols(), mat_nd.cols());
-		assert_eq!(mat.data_typed::<u8>()?, mat_nd.data_typed::<u8>()?);
+#[test]
+fn mat_strided_bytes() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(4, 5, u16::opencv_type(), Scalar::all(3.))?;
+	let strided = mat.strided_bytes()?;
+	assert_eq!(mat.data(), strided.ptr);
+	assert_eq!(4, strided.rows);
+	assert_eq!(10, strided.row_bytes);
+	assert_eq!(10, strided.stride);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 3))?;
+	let strided = roi.strided_bytes()?;
+	assert_eq!(roi.data(), strided.ptr);
+	assert_eq!(3, strided.rows);
+	assert_eq!(4, strided.row_bytes);
+	assert_eq!(10, strided.stride);
+
+	let mat = Mat::new_nd_with_default(&[2, 2, 2], u8::opencv_type(), Scalar::all(0.))?;
+	assert_matches!(
+		mat.strided_bytes(),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
 
-		let mat_3d = Mat::new_nd_with_data(&[1, 2, 3], &src_u8[..6])?;
-		assert_eq!(3, mat_3d.dims());
-		assert_eq!(-1, mat_3d.rows());
-		assert_eq!(-1, mat_3d.cols());
-		let mat_size = mat_3d.mat_size();
-		assert_eq!(1, mat_size.get(0)?);
-		assert_eq!(2, mat_size.get(1)?);
-		assert_eq!(3, mat_size.get(2)?);
-	}
+#[test]
+fn mat_windows() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	let windows = mat.windows(Size::new(3, 2))?;
+	assert_eq!(4, windows.len());
+	let windows = windows
+		.map(|(pos, roi)| Ok((pos, roi.to_vec_2d::<i32>()?)))
+		.collect::<Result<Vec<_>>>()?;
+	assert_eq!(
+		vec![
+			(Point::new(0, 0), vec![vec![1, 2, 3], vec![5, 6, 7]]),
+			(Point::new(1, 0), vec![vec![2, 3, 4], vec![6, 7, 8]]),
+			(Point::new(0, 1), vec![vec![5, 6, 7], vec![9, 10, 11]]),
+			(Point::new(1, 1), vec![vec![6, 7, 8], vec![10, 11, 12]]),
+		],
+		windows
+	);
+	assert_eq!(1, mat.windows(Size::new(4, 3))?.count());
+	assert_eq!(12, mat.windows(Size::new(1, 1))?.count());
 
-	{
-		let mut src_u8 = src_u8;
+	assert_matches!(
+		mat.windows(Size::new(0, 1)),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	assert_matches!(
+		mat.windows(Size::new(5, 1)),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
 
-		let mut mat = Mat::new_rows_cols_with_data_mut(5, 2, &mut src_u8)?;
-		*mat.at_2d_mut::<u8>(3, 0)? = 222;
-		assert_eq!(222, src_u8[6]);
+#[test]
+fn mat_debug_with_limit() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let full = format!("{:?}", mat.debug_with_limit(10));
+	assert!(full.starts_with("Mat { type: "));
+	assert!(full.ends_with(", data: [1, 2, 3, 4, 5, 6] }"));
+	assert!(format!("{:?}", mat.debug_with_limit(4)).ends_with(", data: [1, 2, ..., 5, 6] }"));
+	assert!(format!("{:?}", mat.debug_with_limit(3)).ends_with(", data: [1, 2, ..., 6] }"));
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	assert!(format!("{:?}", roi.debug_with_limit(10)).ends_with(", data: [2, 3, 5, 6] }"));
+	Ok(())
+}
 
-		let mut mat_sz = Mat::new_size_with_data_mut(Size::new(2, 5), &mut src_u8)?;
-		*mat_sz.at_2d_mut::<u8>(3, 0)? = 211;
-		assert_eq!(211, src_u8[6]);
+#[test]
+fn mat_continuous() -> Result<()> {
+	let s: Vec<Vec<f32>> = vec![vec![1., 2., 3.], vec![4., 5., 6.], vec![7., 8., 9.]];
 
-		let mut mat = Mat::new_nd_with_data_mut(&[5, 2], &mut src_u8)?;
-		*mat.at_nd_mut::<u8>(&[3, 0])? = 111;
-		assert_eq!(111, src_u8[6]);
-	}
+	let mut mat = Mat::from_slice_2d(&s)?;
 
 	{
-		let mut src_u8 = src_u8;
-		let mut mat = Mat::from_slice_mut(&mut src_u8)?;
-		core::divide_def(100., &[2; 10], &mut mat)?;
-		assert_eq!([50; 10], src_u8);
-	}
+		let sub_mat_non_cont = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		assert_eq!(mat.typ(), sub_mat_non_cont.typ());
+		assert_eq!(2, sub_mat_non_cont.rows());
+		assert_eq!(2, sub_mat_non_cont.cols());
+		assert!(sub_mat_non_cont.is_submatrix());
+		assert!(!sub_mat_non_cont.is_continuous());
+		assert_eq!(5., *sub_mat_non_cont.at_2d::<f32>(0, 0)?);
+		assert_eq!(6., *sub_mat_non_cont.at_2d::<f32>(0, 1)?);
+		assert_eq!(8., *sub_mat_non_cont.at_2d::<f32>(1, 0)?);
+		assert_eq!(9., *sub_mat_non_cont.at_2d::<f32>(1, 1)?);
 
-	{
-		let src: &[u8] = &[];
-		let mat_res = Mat::new_nd_with_data(&[], src);
-		assert_matches!(
-			mat_res,
-			Err(Error {
-				code: core::StsUnmatchedSizes,
-				..
-			})
-		);
+		let vec = sub_mat_non_cont.to_vec_2d::<f32>()?;
+		assert_eq!(5., vec[0][0]);
+		assert_eq!(6., vec[0][1]);
+		assert_eq!(8., vec[1][0]);
+		assert_eq!(9., vec[1][1]);
+
+		let mat_clone = sub_mat_non_cont.try_clone()?;
+		assert_eq!(mat.typ(), mat_clone.typ());
+		assert_eq!(2, mat_clone.rows());
+		assert_eq!(2, mat_clone.cols());
+		assert!(!mat_clone.is_submatrix());
+		assert!(mat_clone.is_continuous());
+		assert_eq!(5., *mat_clone.at_2d::<f32>(0, 0)?);
+		assert_eq!(6., *mat_clone.at_2d::<f32>(0, 1)?);
+		assert_eq!(8., *mat_clone.at_2d::<f32>(1, 0)?);
+		assert_eq!(9., *mat_clone.at_2d::<f32>(1, 1)?);
 	}
 
 	{
-		let src = [1u8; 20];
-		let src_mat = Mat::new_rows_cols_with_data(4, 5, &src)?;
-		let mut dst = [0u8; 10];
-		let mut dst_mat = Mat::new_rows_cols_with_data_mut(2, 5, &mut dst)?;
-		assert_eq!(Size::new(5, 2), dst_mat.size()?);
-		// this reallocates a `Mat` and disconnects in from the `dst` internally, so it's left untouched
-		imgproc::cvt_color_def(&src_mat, &mut dst_mat, imgproc::COLOR_GRAY2BGR)?;
-		assert_eq!(3, dst_mat.channels());
-		assert_eq!(Size::new(5, 4), dst_mat.size()?);
-		assert_eq!([0; 10], dst);
+		let sub_mat_cont = Mat::roi(&mat, Rect::new(0, 1, 3, 2))?;
+		assert_eq!(mat.typ(), sub_mat_cont.typ());
+		assert_eq!(2, sub_mat_cont.rows());
+		assert_eq!(3, sub_mat_cont.cols());
+		assert!(sub_mat_cont.is_submatrix());
+		assert!(sub_mat_cont.is_continuous());
+		assert_eq!(4., *sub_mat_cont.at_2d::<f32>(0, 0)?);
+		assert_eq!(6., *sub_mat_cont.at_2d::<f32>(0, 2)?);
+		assert_eq!(7., *sub_mat_cont.at_2d::<f32>(1, 0)?);
+		assert_eq!(9., *sub_mat_cont.at_2d::<f32>(1, 2)?);
+
+		let vec = sub_mat_cont.to_vec_2d::<f32>()?;
+		assert_eq!(4., vec[0][0]);
+		assert_eq!(6., vec[0][2]);
+		assert_eq!(7., vec[1][0]);
+		assert_eq!(9., vec[1][2]);
 	}
 
 	{
-		let mat_res = Mat::new_rows_cols_with_data(3, 3, &src_u8);
-		assert_matches!(
-			mat_res,
-			Err(Error {
-				code: core::StsUnmatchedSizes,
-				..
-			})
-		);
-
-		let mat_res = Mat::new_size_with_data(Size::new(3, 3), &src_u8);
+		let mut sub_mat_non_cont = Mat::roi_mut(&mut mat, Rect::new(1, 1, 1, 2))?;
+		assert!(!sub_mat_non_cont.is_continuous());
 		assert_matches!(
-			mat_res,
+			sub_mat_non_cont.data_typed::<f32>(),
 			Err(Error {
 				code: core::StsUnmatchedSizes,
 				..
 			})
 		);
-
-		let mat_res = Mat::new_nd_with_data(&[3, 3], &src_u8);
 		assert_matches!(
-			mat_res,
+			sub_mat_non_cont.data_typed_mut::<f32>(),
 			Err(Error {
 				code: core::StsUnmatchedSizes,
 				..
@@ -1086,85 +1086,2616 @@ fn mat_from_slice() -> Result<()> {
 		);
 	}
 
-	{
-		let mat = Mat::new_rows_cols_with_data(2, 2, &src_point)?;
-		assert_eq!(4, mat.total());
-		assert_eq!(2, mat.rows());
-		assert_eq!(2, mat.cols());
-		assert_eq!(Point2d::new(50.5, 60.6), *mat.at_2d(1, 0)?);
-	}
-
 	Ok(())
 }
 
 #[test]
-fn mat_from_slice_2d() -> Result<()> {
-	{
-		let src = [&[1, 2, 3, 4], &[5, 6, 7, 8], &[9, 10, 11, 12], &[13, 14, 15, 16u8]];
-		let mat = Mat::from_slice_2d(&src)?;
-		assert_eq!(4, mat.rows());
-		assert_eq!(4, mat.cols());
-		assert_eq!(16, mat.total());
-	}
+fn mat_merge_split() -> Result<()> {
+	let mut src = Vector::<Mat>::new();
+	src.push(Mat::new_rows_cols_with_default(1, 3, u8::opencv_type(), 1.into())?);
+	src.push(Mat::new_rows_cols_with_default(1, 3, u8::opencv_type(), 2.into())?);
+	let mut merged = Mat::default();
+	core::merge(&src, &mut merged)?;
+	assert_eq!(merged.typ(), Vec2b::opencv_type());
+	assert_eq!(merged.at_2d::<Vec2b>(0, 1)?[0], 1);
+	assert_eq!(merged.at_2d::<Vec2b>(0, 2)?[1], 2);
 
-	{
-		// first row is shorter
-		let src = &[
-			[1, 2].as_slice(),
-			[5, 6, 7, 8].as_slice(),
-			[9, 10, 11, 12].as_slice(),
-			[13, 14, 15, 16u8].as_slice(),
-		];
-		let mat_res = Mat::from_slice_2d(src);
-		assert_matches!(
-			mat_res,
-			Err(Error {
-				code: core::StsUnmatchedSizes,
-				..
-			})
-		);
-	}
+	let mut split = Vector::<Mat>::new();
+	core::split(&merged, &mut split)?;
+	assert_eq!(2, split.len());
+	let mat = split.get(0)?;
+	assert_eq!(u8::opencv_type(), mat.typ());
+	assert_eq!(1, mat.channels());
+	assert_eq!(1, *mat.at_2d::<u8>(0, 2)?);
+	let mat = split.get(1)?;
+	assert_eq!(u8::opencv_type(), mat.typ());
+	assert_eq!(1, mat.channels());
+	assert_eq!(2, *mat.at_2d::<u8>(0, 0)?);
 
 	Ok(())
 }
 
 #[test]
-fn mat_custom_data_type() -> Result<()> {
-	#[repr(C)]
-	#[derive(Copy, Clone, Debug)]
-	struct A {
-		a: i16,
-		b: i16,
+fn mat_from_data() -> Result<()> {
+	let mut bytes = PIXEL.to_vec();
+	assert_eq!(90, bytes.len());
+
+	{
+		let src = unsafe {
+			Mat::new_rows_cols_with_data_unsafe_def(
+				1,
+				PIXEL.len().try_into()?,
+				u8::opencv_type(),
+				bytes.as_mut_ptr().cast::<c_void>(),
+			)?
+		};
+		assert_eq!(Size::new(PIXEL.len().try_into()?, 1), src.size()?);
+		assert_eq!(PIXEL.len(), src.total());
+		let row = src.at_row::<u8>(0)?;
+		assert_eq!(0x89, row[0]);
+		assert_eq!(0x50, row[1]);
+		assert_eq!(0x1A, row[6]);
+		assert_eq!(0x0D, row[11]);
+		assert_eq!(0x82, row[89]);
 	}
 
-	unsafe impl DataType for A {
-		fn opencv_depth() -> i32 {
-			core::CV_16S
-		}
+	{
+		let src = unsafe { Mat::new_nd_with_data_unsafe_def(&[3, 5, 6], u8::opencv_type(), bytes.as_mut_ptr().cast::<c_void>())? };
+		assert_eq!(Size::new(5, 3), src.size()?);
+		assert_eq!(PIXEL.len(), src.total());
+		assert_eq!(0x89, *src.at_3d::<u8>(0, 0, 0)?);
+		assert_eq!(0x50, *src.at_3d::<u8>(0, 0, 1)?);
+		assert_eq!(0x1A, *src.at_3d::<u8>(0, 1, 0)?);
+		assert_eq!(0x0D, *src.at_3d::<u8>(0, 1, 5)?);
+		assert_eq!(0x82, *src.at_3d::<u8>(2, 4, 5)?);
+	}
 
-		fn opencv_channels() -> i32 {
-			2
-		}
+	{
+		let mut bytes = bytes.clone();
+		let mut mat = unsafe {
+			Mat::new_rows_cols_with_data_unsafe_def(
+				1,
+				bytes.len().try_into()?,
+				u8::opencv_type(),
+				bytes.as_mut_ptr().cast::<c_void>(),
+			)?
+		};
+		assert_eq!(mat.data(), bytes.as_ptr());
+		bytes[0] = 0xFF;
+		assert_eq!(0xFF, *mat.at::<u8>(0)?);
+		mat.resize_with_default(100, 0.into())?;
+		assert_ne!(mat.data(), bytes.as_ptr());
+		bytes[0] = 0xAA;
+		let row = mat.at_row::<u8>(0)?;
+		assert_eq!(0xFF, row[0]);
+		assert_eq!(0x50, row[1]);
+		assert_eq!(0x1A, row[6]);
+		assert_eq!(0x0D, row[11]);
+		assert_eq!(0x82, row[89]);
+		let row = mat.at_row::<u8>(1)?;
+		assert_eq!(0, row[1]);
+		assert_eq!(0, row[6]);
+		assert_eq!(0, row[89]);
 	}
+	Ok(())
+}
 
-	let m = Mat::new_rows_cols_with_default(10, 10, Vec2s::opencv_type(), (-10, 20).into())?;
-	let data = m.data_typed::<A>()?;
-	assert!(!data.is_empty());
-	assert!(data.iter().all(|el| el.a == -10 && el.b == 20));
+#[test]
+fn mat_from_matexpr() -> Result<()> {
+	{
+		let mat = Mat::zeros(3, 4, f32::opencv_type())?.to_mat()?;
+		assert_eq!(4, mat.cols());
+		assert_eq!(3, mat.rows());
+		assert_eq!(0., *mat.at_2d::<f32>(0, 0)?);
+		assert_eq!(0., *mat.at_2d::<f32>(1, 1)?);
+		assert_eq!(0., *mat.at_2d::<f32>(2, 3)?);
+	}
+
+	{
+		let mat = Mat::ones_nd(&[6, 5], f32::opencv_type())?.to_mat()?;
+		assert_eq!(5, mat.cols());
+		assert_eq!(6, mat.rows());
+		assert_eq!(1., *mat.at_2d::<f32>(0, 0)?);
+		assert_eq!(1., *mat.at_2d::<f32>(1, 1)?);
+		assert_eq!(1., *mat.at_2d::<f32>(5, 4)?);
+	}
 	Ok(())
 }
 
 #[test]
-fn mat_reshape() -> Result<()> {
-	let mut slice = [1, 2, 3, 4, 5, 6];
-	let mut mat = Mat::from_slice_mut(&mut slice)?;
+fn mat_map_typed() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let mapped = mat.map_typed(|x: u8| f32::from(x) / 2.)?;
+	assert_eq!(f32::opencv_type(), mapped.typ());
+	assert_eq!(mat.size()?, mapped.size()?);
+	assert_eq!(&[0.5, 1., 1.5, 2., 2.5, 3.], mapped.data_typed()?);
 
-	let mut mat2 = mat.reshape_mut(1, 2)?;
-	assert_eq!(mat2.cols(), 3);
-	assert_eq!(mat2.rows(), 2);
-	*mat2.at_2d_mut(1, 0)? = 10;
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	let mapped = roi.map_typed(|x: u8| i32::from(x) * 10)?;
+	assert_eq!(&[20, 30, 50, 60], mapped.data_typed()?);
 
-	assert_eq!(&[1, 2, 3, 10, 5, 6], mat2.data_typed::<i32>()?);
+	let mapped = Mat::default().map_typed(|x: u8| f64::from(x))?;
+	assert_eq!(0, mapped.total());
+
+	assert_matches!(
+		mat.map_typed(|x: u16| x),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_sum_as_f64() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 255]])?;
+	assert_eq!(270., mat.sum_as_f64::<u8>()?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	assert_eq!(265., roi.sum_as_f64::<u8>()?);
+
+	let mat = Mat::from_slice(&[0.5f32, -1.5, 2.25])?;
+	assert_eq!(1.25, mat.sum_as_f64::<f32>()?);
+	assert_eq!(0., Mat::default().sum_as_f64::<u8>()?);
+
+	assert_matches!(
+		mat.sum_as_f64::<f64>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	let mat = Mat::new_rows_cols_with_default(2, 2, Vec3d::opencv_type(), Scalar::all(1.))?;
+	assert_matches!(
+		mat.sum_as_f64::<f64>(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_position_of() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 3]])?;
+	assert_eq!(Some(Point::new(2, 0)), mat.position_of(&3)?);
+	assert_eq!(Some(Point::new(1, 1)), mat.position_of(&5)?);
+	assert_eq!(None, mat.position_of(&7)?);
+
+	let roi = Mat::roi(&mat, Rect::new(0, 1, 3, 1))?;
+	assert_eq!(Some(Point::new(2, 0)), roi.position_of(&3)?);
+
+	assert_eq!(None, Mat::default().position_of(&0u8)?);
+	assert_matches!(
+		mat.position_of(&3u8),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_min_max_typed() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[3i16, -7, 5], [12, 0, -1]])?;
+	assert_eq!((-7, 12), mat.min_max_typed::<i16>()?);
+
+	let roi = Mat::roi(&mat, Rect::new(2, 0, 1, 2))?;
+	assert_eq!((-1, 5), roi.min_max_typed::<i16>()?);
+
+	let mat = Mat::from_slice(&[Vec2b::from([1, 2]), Vec2b::from([0, 9]), Vec2b::from([1, 0])])?;
+	assert_eq!((Vec2b::from([0, 9]), Vec2b::from([1, 2])), mat.min_max_typed::<Vec2b>()?);
+
+	assert_matches!(
+		Mat::default().min_max_typed::<u8>(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	assert_matches!(
+		mat.min_max_typed::<u8>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_zeros_ones_like() -> Result<()> {
+	{
+		let src = Mat::new_rows_cols_with_default(2, 3, Vec3f::opencv_type(), Scalar::all(5.))?;
+		let zeros = src.zeros_like()?;
+		assert_eq!(src.typ(), zeros.typ());
+		assert_eq!(src.size()?, zeros.size()?);
+		assert!(zeros.data_typed::<Vec3f>()?.iter().all(|x| *x == Vec3f::all(0.)));
+		let ones = src.ones_like()?;
+		assert_eq!(src.typ(), ones.typ());
+		assert!(ones.data_typed::<Vec3f>()?.iter().all(|x| *x == Vec3f::all(1.)));
+	}
+
+	{
+		let src = Mat::new_nd_with_default(&[2, 3, 4], u16::opencv_type(), 7.into())?;
+		let zeros = src.zeros_like()?;
+		assert_eq!([2, 3, 4], *zeros.mat_size());
+		assert!(zeros.data_typed::<u16>()?.iter().all(|x| *x == 0));
+	}
+
+	assert!(Mat::default().zeros_like()?.empty());
+	Ok(())
+}
+
+#[test]
+fn mat_const_iterator() -> Result<()> {
+	{
+		let mat = Mat::from_slice(&[1, 2, 3, 4])?;
+		let mut pos = [0, 0];
+		let mut iter = MatConstIterator::over(&mat)?;
+		unsafe {
+			iter.pos_to(pos.as_mut_ptr())?;
+		}
+		assert_eq!(iter.typ(), mat.typ());
+		assert_eq!(1, *iter.current::<i32>()?);
+		assert_eq!(Point::new(0, 0), iter.pos()?);
+		assert_eq!([0, 0], pos);
+		assert!(iter.has_elements());
+		iter.seek(1, true)?;
+		unsafe {
+			iter.pos_to(pos.as_mut_ptr())?;
+		}
+		assert_eq!(2, *iter.current::<i32>()?);
+		assert_eq!(Point::new(1, 0), iter.pos()?);
+		assert_eq!([0, 1], pos);
+		assert!(iter.has_elements());
+		iter.seek(1, true)?;
+		unsafe {
+			iter.pos_to(pos.as_mut_ptr())?;
+		}
+		assert_eq!(3, *iter.current::<i32>()?);
+		assert_eq!(Point::new(2, 0), iter.pos()?);
+		assert_eq!([0, 2], pos);
+		assert!(iter.has_elements());
+		iter.seek(1, true)?;
+		unsafe {
+			iter.pos_to(pos.as_mut_ptr())?;
+		}
+		assert_eq!(4, *iter.current::<i32>()?);
+		assert_eq!(Point::new(3, 0), iter.pos()?);
+		assert_eq!([0, 3], pos);
+		assert!(iter.has_elements());
+		iter.seek(1, true)?;
+		unsafe {
+			iter.pos_to(pos.as_mut_ptr())?;
+		}
+		assert_matches!(
+			iter.current::<i32>(),
+			Err(Error {
+				code: core::StsOutOfRange,
+				..
+			})
+		);
+		assert_eq!(Point::new(0, 1), iter.pos()?);
+		assert_eq!([1, 0], pos);
+		assert!(!iter.has_elements());
+		iter.seek(1, true)?;
+		unsafe {
+			iter.pos_to(pos.as_mut_ptr())?;
+		}
+		assert_matches!(
+			iter.current::<i32>(),
+			Err(Error {
+				code: core::StsOutOfRange,
+				..
+			})
+		);
+		assert_eq!(Point::new(0, 1), iter.pos()?);
+		assert_eq!([1, 0], pos);
+		assert!(!iter.has_elements());
+	}
+
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+		let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+		let mut iter = MatConstIterator::over(&roi)?;
+		iter.try_seek(3)?;
+		assert_eq!(6, *iter.current::<i32>()?);
+		iter.try_seek(-2)?;
+		assert_eq!(3, *iter.current::<i32>()?);
+		iter.try_seek(3)?;
+		assert!(!iter.has_elements());
+		assert_matches!(
+			iter.try_seek(1),
+			Err(Error {
+				code: core::StsOutOfRange,
+				..
+			})
+		);
+		assert_matches!(
+			iter.try_seek(-5),
+			Err(Error {
+				code: core::StsOutOfRange,
+				..
+			})
+		);
+		iter.try_seek(-4)?;
+		assert_eq!(2, *iter.current::<i32>()?);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn mat_iterator() -> Result<()> {
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
+		for (pos, x) in mat.iter::<i32>()? {
+			match pos {
+				Point { x: 0, y: 0 } => assert_eq!(x, 1),
+				Point { x: 1, y: 0 } => assert_eq!(x, 2),
+				Point { x: 0, y: 1 } => assert_eq!(x, 3),
+				Point { x: 1, y: 1 } => assert_eq!(x, 4),
+				_ => panic!("Too many elements"),
+			}
+		}
+		for (pos, x) in MatIter::<i32>::new(MatConstIterator::with_start(&mat, Point::new(1, 0))?)? {
+			match pos {
+				Point { x: 1, y: 0 } => assert_eq!(x, 2),
+				Point { x: 0, y: 1 } => assert_eq!(x, 3),
+				Point { x: 1, y: 1 } => assert_eq!(x, 4),
+				_ => panic!("Too many elements"),
+			}
+		}
+	}
 
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		for (pos, x) in roi.iter::<i32>()? {
+			match pos {
+				Point { x: 0, y: 0 } => assert_eq!(x, 6),
+				Point { x: 1, y: 0 } => assert_eq!(x, 7),
+				Point { x: 0, y: 1 } => assert_eq!(x, 10),
+				Point { x: 1, y: 1 } => assert_eq!(x, 11),
+				_ => panic!("Too many elements"),
+			}
+		}
+	}
+
+	{
+		let mut mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
+		for (pos, x) in mat.iter_mut::<i32>()? {
+			*x *= pos.x + pos.y;
+		}
+		assert_eq!([0, 2, 3, 8], mat.data_typed::<i32>()?);
+	}
+
+	{
+		let mut mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 1, 2, 2))?;
+		for (pos, x) in roi.iter_mut::<i32>()? {
+			*x += pos.x + pos.y;
+		}
+		assert_eq!(
+			[1, 2, 3, 4, 5, 6, 8, 8, 9, 11, 13, 12, 13, 14, 15, 16],
+			mat.data_typed::<i32>()?
+		);
+	}
+
+	{
+		let mat = Mat::from_slice::<u8>(&[])?;
+		#[allow(clippy::never_loop)]
+		for _ in mat.iter::<u8>()? {
+			panic!("Mat must be empty");
+		}
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_iterator_double_ended() -> Result<()> {
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+		let mut iter = mat.iter::<i32>()?;
+		assert_eq!(6, iter.len());
+		assert_eq!(Some((Point::new(0, 0), 1)), iter.next());
+		assert_eq!(Some((Point::new(2, 1), 6)), iter.next_back());
+		assert_eq!(4, iter.len());
+		assert_eq!(Some((Point::new(1, 1), 5)), iter.next_back());
+		assert_eq!(Some((Point::new(1, 0), 2)), iter.next());
+		assert_eq!(Some((Point::new(0, 1), 4)), iter.next_back());
+		assert_eq!(Some((Point::new(2, 0), 3)), iter.next());
+		assert_eq!(0, iter.len());
+		assert_eq!(None, iter.next());
+		assert_eq!(None, iter.next_back());
+	}
+
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		let items = roi.iter::<i32>()?.rev().map(|(_, x)| x).collect::<Vec<_>>();
+		assert_eq!(vec![11, 10, 7, 6], items);
+		let iter = MatIter::<i32>::new(MatConstIterator::with_start(&mat, Point::new(2, 3))?)?;
+		assert_eq!(2, iter.len());
+		assert_eq!(vec![16, 15], iter.rev().map(|(_, x)| x).collect::<Vec<_>>());
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_iterator_clone() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+	let mut iter = mat.iter::<i32>()?;
+	assert_eq!(Some((Point::new(0, 0), 1)), iter.next());
+	assert_eq!(Some((Point::new(2, 1), 6)), iter.next_back());
+	let mut peek = iter.clone();
+	assert_eq!(Some((Point::new(1, 0), 2)), peek.next());
+	assert_eq!(Some((Point::new(2, 0), 3)), peek.next());
+	assert_eq!(2, peek.len());
+	assert_eq!(4, iter.len());
+	assert_eq!(vec![2, 3, 4, 5], iter.map(|(_, x)| x).collect::<Vec<_>>());
+	assert_eq!(vec![5, 4], peek.rev().map(|(_, x)| x).collect::<Vec<_>>());
+	Ok(())
+}
+
+#[test]
+fn mat_iterator_values_positions() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1f32, 2.], [3., 4.5]])?;
+	assert_eq!(10.5, mat.iter::<f32>()?.values().sum::<f32>());
+	assert_eq!(vec![4.5, 3.], mat.iter::<f32>()?.values().rev().take(2).collect::<Vec<_>>());
+	assert_eq!(
+		vec![Point::new(0, 0), Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)],
+		mat.iter::<f32>()?.positions().collect::<Vec<_>>()
+	);
+	assert_eq!(4, mat.iter::<f32>()?.positions().len());
+	Ok(())
+}
+
+#[test]
+fn mat_iterator_rev() -> Result<()> {
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
+		let items = mat.iter_rev::<i32>()?.collect::<Vec<_>>();
+		assert_eq!(
+			vec![
+				(Point::new(1, 1), 4),
+				(Point::new(0, 1), 3),
+				(Point::new(1, 0), 2),
+				(Point::new(0, 0), 1)
+			],
+			items
+		);
+	}
+
+	{
+		let mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12], [13, 14, 15, 16]])?;
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		let items = roi.iter_rev::<i32>()?.map(|(_, x)| x).collect::<Vec<_>>();
+		assert_eq!(vec![11, 10, 7, 6], items);
+	}
+
+	{
+		let mat = Mat::from_slice::<u8>(&[])?;
+		assert_eq!(0, mat.iter_rev::<u8>()?.count());
+		let mat = Mat::from_slice(&[1u8, 2])?;
+		assert_matches!(
+			mat.iter_rev::<i32>(),
+			Err(Error {
+				code: core::StsUnmatchedFormats,
+				..
+			})
+		);
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_locate_roi() -> Result<()> {
+	let mat = Mat::from_slice(&[1, 2, 3, 4])?;
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 1))?;
+	let mut sz = Size::default();
+	let mut ofs = Point::default();
+	roi.locate_roi(&mut sz, &mut ofs)?;
+	assert_eq!(sz, Size::new(4, 1));
+	assert_eq!(ofs, Point::new(1, 0));
+	Ok(())
+}
+
+#[test]
+fn mat_roi() -> Result<()> {
+	let mut mat = Mat::from_slice(&[1, 2, 3, 4])?.try_clone()?;
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 1))?;
+	assert_eq!(2, *roi.at(0)?);
+	assert_eq!(3, *roi.at(1)?);
+
+	let roi = mat.roi(Rect::new(1, 0, 2, 1))?;
+	assert_eq!(2, *roi.at(0)?);
+	assert_eq!(3, *roi.at(1)?);
+
+	let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 0, 2, 1))?;
+	assert_eq!(2, *roi.at(0)?);
+	assert_eq!(3, *roi.at(1)?);
+	*roi.at_mut(0)? = 10;
+	*roi.at_mut(1)? = 11;
+	assert_eq!(&[1, 10, 11, 4], mat.data_typed::<i32>()?);
+
+	let mut roi = mat.roi_mut(Rect::new(1, 0, 2, 1))?;
+	assert_eq!(10, *roi.at(0)?);
+	assert_eq!(11, *roi.at(1)?);
+	*roi.at_mut(0)? = 20;
+	*roi.at_mut(1)? = 21;
+	assert_eq!(&[1, 20, 21, 4], mat.data_typed::<i32>()?);
+
+	Ok(())
+}
+
+#[test]
+fn mat_roi_2() -> Result<()> {
+	let mut mat = Mat::from_slice(&[1, 2, 3, 4])?.try_clone()?;
+	let (mut roi1, mut roi2) = Mat::roi_2_mut(&mut mat, Rect::new(0, 0, 2, 1), Rect::new(2, 0, 2, 1))?;
+	assert_eq!(1, *roi1.at(0)?);
+	assert_eq!(2, *roi1.at(1)?);
+	assert_eq!(3, *roi2.at(0)?);
+	assert_eq!(4, *roi2.at(1)?);
+
+	mem::swap(roi1.at_mut::<i32>(0)?, roi2.at_mut::<i32>(0)?);
+	mem::swap(roi1.at_mut::<i32>(1)?, roi2.at_mut::<i32>(1)?);
+	assert_eq!(3, *mat.at(0)?);
+	assert_eq!(4, *mat.at(1)?);
+	assert_eq!(1, *mat.at(2)?);
+	assert_eq!(2, *mat.at(3)?);
+
+	assert_matches!(
+		Mat::roi_2_mut(&mut mat, Rect::new(0, 0, 3, 1), Rect::new(2, 0, 2, 1)),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+
+	Ok(())
+}
+
+#[test]
+fn mat_roi_n() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3, 4], [5, 6, 7, 8]])?;
+	let rois = [
+		Rect::new(0, 0, 2, 1),
+		Rect::new(2, 0, 2, 1),
+		Rect::new(0, 1, 2, 1),
+		Rect::new(2, 1, 2, 1),
+	];
+	{
+		let mut tiles = Mat::roi_n_mut(&mut mat, &rois)?;
+		assert_eq!(4, tiles.len());
+		for (i, tile) in tiles.iter_mut().enumerate() {
+			tile.set_scalar(Scalar::all(i as f64))?;
+		}
+	}
+	assert_eq!(&[0, 0, 1, 1, 2, 2, 3, 3], mat.data_typed::<i32>()?);
+
+	assert!(Mat::roi_n_mut(&mut mat, &[])?.is_empty());
+
+	assert_matches!(
+		Mat::roi_n_mut(
+			&mut mat,
+			&[Rect::new(0, 0, 1, 1), Rect::new(2, 0, 2, 2), Rect::new(3, 1, 1, 1)]
+		),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_split_rows() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2], [3, 4], [5, 6]])?;
+	{
+		let (mut top, mut bottom) = Mat::split_rows_mut(&mut mat, 1)?;
+		assert_eq!(Size::new(2, 1), top.size()?);
+		assert_eq!(Size::new(2, 2), bottom.size()?);
+		top.set_scalar(10.into())?;
+		*bottom.at_2d_mut::<i32>(1, 1)? = 60;
+	}
+	assert_eq!(&[10, 10, 3, 4, 5, 60], mat.data_typed::<i32>()?);
+
+	{
+		let (top, bottom) = Mat::split_rows_mut(&mut mat, 3)?;
+		assert_eq!(3, top.rows());
+		assert!(bottom.empty());
+	}
+
+	assert_matches!(
+		Mat::split_rows_mut(&mut mat, 4),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::split_rows_mut(&mut mat, -1),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_raw_parts() -> Result<()> {
+	{
+		let mat = Mat::from_slice_2d(&[[1u16, 2, 3], [4, 5, 6]])?;
+		let data = mat.data();
+		let parts = mat.into_raw_parts()?;
+		assert_eq!(data, parts.data.cast_const());
+		assert_eq!(12, parts.len);
+		assert_eq!(2, parts.rows);
+		assert_eq!(3, parts.cols);
+		assert_eq!(u16::opencv_type(), parts.typ);
+		let bytes = unsafe { std::slice::from_raw_parts(parts.data.cast::<u16>(), parts.len / 2) };
+		assert_eq!(&[1, 2, 3, 4, 5, 6], bytes);
+		let mat = unsafe { Mat::from_raw_parts(parts) };
+		assert_eq!(data, mat.data());
+		assert_eq!(5, *mat.at_2d::<u16>(1, 1)?);
+	}
+
+	{
+		let mut mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
+		let roi = mat.adjust_roi(0, 0, 0, -1)?;
+		assert!(!roi.is_continuous());
+		assert_matches!(
+			roi.into_raw_parts(),
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_convert() -> Result<()> {
+	let mat = Mat::from_slice(&[1, 2, 3, 4])?;
+	let mut mat_ = mat.try_clone()?.try_into_typed::<i32>()?;
+	assert_eq!(3, *mat_.at(2)?);
+	*mat_.at_mut(3)? = 8;
+	assert_eq!(8, *mat_.at(3)?);
+	assert_eq!(mat.typ(), mat_.typ());
+	assert_eq!(mat.size()?, mat_.size()?);
+	let mat_back = mat_.into_untyped();
+	assert_eq!(mat.size()?, mat_back.size()?);
+	Ok(())
+}
+
+#[test]
+fn mat_typed_index() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?.try_into_typed::<i32>()?;
+	assert_eq!(2, mat[(0, 1)]);
+	assert_eq!(6, mat[(1, 2)]);
+	mat[(1, 0)] = 40;
+	assert_eq!(40, *mat.at_2d(1, 0)?);
+	assert!(std::panic::catch_unwind(|| mat[(2, 0)]).is_err());
+	assert!(std::panic::catch_unwind(|| mat[(0, -1)]).is_err());
+	Ok(())
+}
+
+#[test]
+fn mat_typed_eq() -> Result<()> {
+	let mat1 = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?.try_into_typed::<i32>()?;
+	let mat2 = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?.try_into_typed::<i32>()?;
+	let mat3 = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 7]])?.try_into_typed::<i32>()?;
+	let mat4 = Mat::from_slice_2d(&[[1, 2], [3, 4], [5, 6]])?.try_into_typed::<i32>()?;
+	assert!(mat1 == mat2);
+	assert!(mat1 != mat3);
+	assert!(mat1 != mat4);
+
+	let mut big = Mat::from_slice_2d(&[[0, 1, 2, 3], [0, 4, 5, 6]])?;
+	let roi = Mat::roi(&big, Rect::new(1, 0, 3, 2))?.try_clone()?;
+	// shares the data with `big`
+	let roi_view = big.adjust_roi(0, 0, -1, 0)?.try_into_typed::<i32>()?;
+	assert!(!roi_view.is_continuous());
+	assert!(roi_view == mat1);
+	assert!(roi.try_into_typed::<i32>()? == mat1);
+
+	let nan = Mat::from_slice(&[f32::NAN])?.try_clone()?.try_into_typed::<f32>()?;
+	assert!(nan != nan.try_clone()?.try_into_typed::<f32>()?);
+	Ok(())
+}
+
+#[test]
+fn mat_typed_from_iter() -> Result<()> {
+	let mat: core::Mat_<f32> = (0..100).map(|x| x as f32).collect();
+	assert_eq!(Size::new(1, 100), mat.size()?);
+	assert_eq!(f32::opencv_type(), mat.typ());
+	assert_eq!(42., *mat.at_2d(42, 0)?);
+
+	let mat: core::Mat_<Vec2b> = std::iter::empty().collect();
+	assert_eq!(0, mat.total());
+	Ok(())
+}
+
+#[test]
+fn mat_typed_extend() -> Result<()> {
+	let mut mat: core::Mat_<i32> = (0..3).collect();
+	mat.extend(3..5);
+	mat.extend([]);
+	assert_eq!(Size::new(1, 5), mat.size()?);
+	assert_eq!(&[0, 1, 2, 3, 4], mat.data_typed()?);
+
+	let mut mat: core::Mat_<Vec2b> = std::iter::empty().collect();
+	mat.extend([Vec2b::from([1, 2]), Vec2b::from([3, 4])]);
+	assert_eq!(Size::new(1, 2), mat.size()?);
+	assert_eq!(Vec2b::from([3, 4]), *mat.at_2d(1, 0)?);
+	Ok(())
+}
+
+#[test]
+fn mat_concat() -> Result<()> {
+	let a = Mat::from_slice_2d(&[[1u8, 2], [3, 4]])?;
+	let b = Mat::from_slice_2d(&[[5u8, 6]])?;
+	let c = Mat::from_slice_2d(&[[7u8], [8]])?;
+
+	let v = core::vconcat_mats(&[a.try_clone()?, b.try_clone()?])?;
+	assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5, 6]], v.to_vec_2d::<u8>()?);
+
+	let h = core::hconcat_mats(&[a.try_clone()?, c.try_clone()?])?;
+	assert_eq!(vec![vec![1, 2, 7], vec![3, 4, 8]], h.to_vec_2d::<u8>()?);
+
+	let empty: [Mat; 0] = [];
+	assert!(core::vconcat_mats(&empty)?.empty());
+	assert!(core::hconcat_mats(&empty)?.empty());
+
+	assert_matches!(
+		core::vconcat_mats(&[a.try_clone()?, c.try_clone()?]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		core::hconcat_mats(&[a.try_clone()?, b.try_clone()?]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	let d = Mat::from_slice_2d(&[[1u16, 2]])?;
+	assert_matches!(
+		core::vconcat_mats(&[a, d]),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_stack() -> Result<()> {
+	let a = Mat::from_slice_2d(&[[1u8, 2], [3, 4]])?;
+	let b = Mat::from_slice(&[5u8, 6])?;
+	let c = Mat::from_slice_2d(&[[7u8], [8]])?;
+
+	assert_eq!(vec![vec![1, 2], vec![3, 4], vec![5, 6]], a.vstack(&b)?.to_vec_2d::<u8>()?);
+	assert_eq!(vec![vec![1, 2, 7], vec![3, 4, 8]], a.hstack(&c)?.to_vec_2d::<u8>()?);
+	assert_eq!(
+		vec![vec![1, 2, 7, 1, 2], vec![3, 4, 8, 3, 4]],
+		a.hstack(&c)?.hstack(&a)?.to_vec_2d::<u8>()?
+	);
+
+	assert_matches!(
+		a.hstack(&b),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		a.vstack(&c),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	let d = Mat::from_slice_2d(&[[1u16, 2]])?;
+	assert_matches!(
+		a.vstack(&d),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_typed_into_iter() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?.try_into_typed::<i32>()?;
+	let mut sum = 0;
+	for (pos, val) in &mat {
+		assert_eq!(*val, pos.y * 2 + pos.x + 1);
+		sum += val;
+	}
+	assert_eq!(10, sum);
+
+	for (pos, val) in &mut mat {
+		*val *= pos.x + 1;
+	}
+	assert_eq!(&[1, 4, 3, 8], mat.data_typed()?);
+
+	let empty = Mat::default().try_into_typed::<u8>()?;
+	assert_eq!(0, (&empty).into_iter().count());
+	Ok(())
+}
+
+#[test]
+fn mat_hstack_vstack() -> Result<()> {
+	let a = Mat::from_slice_2d(&[[1u8, 2], [3, 4]])?;
+	let big = Mat::from_slice_2d(&[[0u8, 5, 6], [0, 7, 8]])?;
+	let b = Mat::roi(&big, Rect::new(1, 0, 2, 2))?;
+	assert!(!b.is_continuous());
+	let stacked = Mat::stack(&[Mat::roi(&a, Rect::new(0, 0, 2, 2))?, b])?;
+	assert_eq!([2, 2, 2], *stacked.mat_size());
+	assert_eq!(u8::opencv_type(), stacked.typ());
+	assert_eq!(
+		vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5, 6], vec![7, 8]]],
+		stacked.to_vec_3d::<u8>()?
+	);
+
+	let empty: [Mat; 0] = [];
+	assert!(Mat::stack(&empty)?.empty());
+
+	assert_matches!(
+		Mat::stack(&[a.try_clone()?, big]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::stack(&[a, Mat::from_slice_2d(&[[1i32, 2], [3, 4]])?]),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_approx_eq() -> Result<()> {
+	let a = Mat::from_slice_2d(&[[1f32, 2.], [3., 4.]])?;
+	let b = Mat::from_slice_2d(&[[1.0001f32, 2.], [3., 3.9999]])?;
+	assert!(core::mat_approx_eq::<f32>(&a, &b, 0.001)?);
+	assert!(!core::mat_approx_eq::<f32>(&a, &b, 0.00001)?);
+	assert_matches!(
+		core::mat_approx_eq::<f32>(&a, &Mat::from_slice(&[1f32, 2., 3., 4.])?, 0.001),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		core::mat_approx_eq::<f64>(&a, &b, 0.001),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+
+	let a = Mat::from_slice(&[Vec3d::from([1., 2., 3.]), Vec3d::from([4., 5., 6.])])?;
+	let b = Mat::from_slice(&[Vec3d::from([1., 2., 3.]), Vec3d::from([4., 5.5, 6.])])?;
+	assert!(core::mat_approx_eq::<Vec3d>(&a, &b, 0.5)?);
+	assert!(!core::mat_approx_eq::<Vec3d>(&a, &b, 0.1)?);
+
+	let nan = Mat::from_slice(&[f64::NAN])?;
+	assert!(!core::mat_approx_eq::<f64>(&nan, &nan, 1.)?);
+	Ok(())
+}
+
+#[test]
+fn mat_mul() -> Result<()> {
+	{
+		let expr = Mat::ones(1, 5, f64::opencv_type())?;
+		let res = core::mul_matexpr_f64(&expr, 4.)?.to_mat()?;
+		assert_eq!(res.typ(), f64::opencv_type());
+		assert_eq!(res.data_typed::<f64>()?, &[4., 4., 4., 4., 4.]);
+	}
+	{
+		let expr = Mat::new_rows_cols_with_default(2, 3, i32::opencv_type(), 9.into())?;
+		let res = core::sub_mat_scalar(&expr, 5.into())?.to_mat()?;
+		assert_eq!(res.typ(), i32::opencv_type());
+		assert_eq!(res.data_typed::<i32>()?, &[4, 4, 4, 4, 4, 4]);
+	}
+	{
+		let mat1 = Mat::from_slice_2d(&[[1f32, 2., 3.], [4., 5., 6.]])?;
+		let mat2 = Mat::from_slice_2d(&[[7f32, 8.], [9., 10.], [11., 12.]])?;
+		let res = core::mul_mat_mat(&mat1, &mat2)?.to_mat()?;
+		assert_eq!(res.typ(), f32::opencv_type());
+		assert_eq!(res.size()?, Size::new(2, 2));
+		assert_eq!(res.data_typed::<f32>()?, &[58., 64., 139., 154.]);
+	}
+	{
+		let mat = Mat::from_slice(&[1u8, 2, 3, 4, 5, 6])?;
+		let res = core::div_mat_f64(&mat, 2.)?.to_mat()?;
+		assert_eq!(res.typ(), u8::opencv_type());
+		assert_eq!(res.data_typed::<u8>()?, &[0, 1, 2, 2, 2, 3]);
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_data() -> Result<()> {
+	{
+		let mat = Mat::from_slice(&[8, 13, 21, 39u8])?;
+		assert_eq!(&[8, 13, 21, 39], mat.data_bytes()?);
+		let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 1))?;
+		assert_eq!(&[13, 21], roi.data_bytes()?);
+	}
+
+	{
+		let mat = Mat::from_slice_2d(&[[6, 7], [16, 17u8]])?;
+		assert_eq!(&[6, 7, 16, 17], mat.data_bytes()?);
+		let roi = Mat::roi(&mat, Rect::new(0, 0, 1, 2))?;
+		assert_matches!(
+			roi.data_bytes(),
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+
+	{
+		let mut mat = Mat::from_slice(&[Vec2b::from([5, 8]), Vec2b::from([13, 21])])?.try_clone()?;
+		assert_eq!(&[5, 8, 13, 21], mat.data_bytes()?);
+		let bytes = mat.data_bytes_mut()?;
+		bytes[1] = 90;
+		assert_eq!(&[5, 90, 13, 21], mat.data_bytes()?);
+		assert_eq!(&[5, 90, 13, 21], unsafe { mat.data_bytes_unchecked() });
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_as_slice() -> Result<()> {
+	{
+		let mut mat = Mat::from_slice_2d(&[[1, 2], [3, 4]])?;
+		assert_eq!(Some([1, 2, 3, 4].as_slice()), mat.as_slice::<i32>());
+		assert_eq!(None, mat.as_slice::<u8>());
+		mat.as_slice_mut::<i32>().expect("Continuous Mat")[3] = 10;
+		assert_eq!(&[1, 2, 3, 10], mat.data_typed::<i32>()?);
+		assert!(mat.as_slice_mut::<f32>().is_none());
+	}
+
+	{
+		let mut mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6]])?;
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(0, 0, 2, 2))?;
+		assert_eq!(None, roi.as_slice::<i32>());
+		assert!(roi.as_slice_mut::<i32>().is_none());
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_fill_from_fn() -> Result<()> {
+	{
+		let mut mat = Mat::new_rows_cols_with_default(2, 3, i32::opencv_type(), 0.into())?;
+		mat.fill_from_fn(|row, col| row * 10 + col)?;
+		assert_eq!(&[0, 1, 2, 10, 11, 12], mat.data_typed::<i32>()?);
+		assert_matches!(
+			mat.fill_from_fn(|_, _| 0u8),
+			Err(Error {
+				code: core::StsUnmatchedFormats,
+				..
+			})
+		);
+	}
+
+	{
+		let mut mat = Mat::new_rows_cols_with_default(3, 3, f32::opencv_type(), 0.into())?;
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 1, 2, 2))?;
+		roi.fill_from_fn(|row, col| (row + col) as f32 + 1.)?;
+		assert_eq!(&[0., 0., 0., 0., 1., 2., 0., 2., 3.], mat.data_typed::<f32>()?);
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_equals() -> Result<()> {
+	let mat1 = Mat::new_rows_cols_with_default(3, 3, i32::opencv_type(), 0.into())?;
+	let mat2 = Mat::new_rows_cols_with_default(3, 3, i32::opencv_type(), 0.into())?;
+	let mat3 = Mat::new_rows_cols_with_default(3, 3, i32::opencv_type(), Scalar::all(1.))?;
+	let res = core::equals_mat_mat(&mat1, &mat2)?.to_mat()?;
+	assert!(res.data_typed::<u8>()?.iter().all(|&e| e != 0));
+	let res = core::equals_mat_mat(&mat1, &mat3)?.to_mat()?;
+	assert!(res.data_typed::<u8>()?.iter().all(|&e| e == 0));
+	Ok(())
+}
+
+#[test]
+fn mat_rgb() -> Result<()> {
+	#![cfg(feature = "rgb")]
+	let m = Mat::new_rows_cols_with_default(3, 3, rgb::RGBA8::opencv_type(), Scalar::all(1.))?;
+	assert_eq!(rgb::RGBA::new(1, 1, 1, 1), *m.at_2d(1, 1)?);
+	assert_matches!(
+		m.at_2d::<rgb::RGB8>(1, 1),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_apply_in_place() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let data = mat.data();
+	mat.apply_in_place(|x: u8| x * 2)?;
+	assert_eq!(data, mat.data());
+	assert_eq!(&[2, 4, 6, 8, 10, 12], mat.data_typed::<u8>()?);
+
+	{
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 0, 2, 2))?;
+		roi.apply_in_place(|x: u8| x + 1)?;
+	}
+	assert_eq!(&[2, 5, 7, 8, 11, 13], mat.data_typed::<u8>()?);
+
+	assert_matches!(
+		mat.apply_in_place(|x: i32| x),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_ndarray() -> Result<()> {
+	#![cfg(feature = "ndarray")]
+	let mut mat = Mat::from_slice_2d(&[[1, 2, 3], [4, 5, 6], [7, 8, 9]])?;
+	{
+		let view = mat.as_ndarray_view::<i32>()?;
+		assert_eq!(&[3, 3], view.shape());
+		assert_eq!(6, view[[1, 2]]);
+		assert_eq!(mat.data().cast(), view.as_ptr());
+	}
+
+	{
+		let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+		let view = roi.as_ndarray_view::<i32>()?;
+		assert_eq!(&[2, 2], view.shape());
+		assert_eq!(&[3, 1], view.strides());
+		assert_eq!(ndarray::arr2(&[[5, 6], [8, 9]]), view);
+	}
+
+	{
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(0, 1, 2, 2))?;
+		let mut view = roi.as_ndarray_view_mut::<i32>()?;
+		view[[1, 0]] = 70;
+		assert_eq!(&[1, 2, 3, 4, 5, 6, 70, 8, 9], mat.data_typed::<i32>()?);
+	}
+
+	{
+		let mut no_data = Mat::new_rows_cols_with_default(5, 0, i32::opencv_type(), 0.into())?;
+		assert_eq!(&[5, 0], no_data.as_ndarray_view::<i32>()?.shape());
+		assert_eq!(&[5, 0], no_data.as_ndarray_view_mut::<i32>()?.shape());
+	}
+
+	assert_matches!(
+		mat.as_ndarray_view::<u8>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_from_ndarray() -> Result<()> {
+	#![cfg(feature = "ndarray")]
+	let arr = ndarray::arr2(&[[1u16, 2, 3], [4, 5, 6]]);
+	let mat = Mat::from_ndarray(arr.view())?;
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert_eq!(&[1, 2, 3, 4, 5, 6], mat.data_typed::<u16>()?);
+
+	let mat = Mat::from_ndarray(arr.t())?;
+	assert_eq!(Size::new(2, 3), mat.size()?);
+	assert_eq!(&[1, 4, 2, 5, 3, 6], mat.data_typed::<u16>()?);
+
+	let mat = Mat::from_ndarray(arr.slice(ndarray::s![.., ..;2]))?;
+	assert_eq!(&[1, 3, 4, 6], mat.data_typed::<u16>()?);
+
+	let empty = ndarray::Array2::<f32>::zeros((0, 4));
+	assert!(Mat::from_ndarray(empty.view())?.empty());
+	Ok(())
+}
+
+#[test]
+fn mat_serde() -> Result<()> {
+	#![cfg(feature = "serde")]
+	{
+		let mat = Mat::from_slice_2d(&[
+			[Vec2s::from([1, 2]), Vec2s::from([3, 4])],
+			[Vec2s::from([5, 6]), Vec2s::from([7, 8])],
+		])?;
+		let json = serde_json::to_string(&mat).expect("Can't serialize");
+		let mat_back: Mat = serde_json::from_str(&json).expect("Can't deserialize");
+		assert_eq!(mat.typ(), mat_back.typ());
+		assert_eq!(*mat.mat_size(), *mat_back.mat_size());
+		assert_eq!(mat.data_typed::<Vec2s>()?, mat_back.data_typed::<Vec2s>()?);
+	}
+
+	{
+		let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+		let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+		let json = serde_json::to_string(&roi).expect("Can't serialize");
+		let mat_back: Mat = serde_json::from_str(&json).expect("Can't deserialize");
+		assert_eq!(&[2, 3, 5, 6], mat_back.data_typed::<u8>()?);
+	}
+
+	{
+		let json = serde_json::to_string(&Mat::default()).expect("Can't serialize");
+		let mat_back: Mat = serde_json::from_str(&json).expect("Can't deserialize");
+		assert!(mat_back.empty());
+	}
+
+	{
+		let json = format!(r#"{{"sizes":[2,2],"typ":{},"data":[1,2,3]}}"#, u8::opencv_type());
+		assert!(serde_json::from_str::<Mat>(&json).is_err());
+		let json = format!(r#"{{"sizes":[-2,-2],"typ":{},"data":[1,2,3,4]}}"#, u8::opencv_type());
+		assert!(serde_json::from_str::<Mat>(&json).is_err());
+		// must fail on the data length check without trying to allocate the Mat
+		let json = format!(r#"{{"sizes":[100000,100000],"typ":{},"data":[]}}"#, u8::opencv_type());
+		assert!(serde_json::from_str::<Mat>(&json).is_err());
+	}
+	Ok(())
+}
+
+#[test]
+fn mat_image_rgb() -> Result<()> {
+	#![cfg(feature = "image")]
+	let mat = Mat::from_slice_2d(&[
+		[Vec3b::from([1, 2, 3]), Vec3b::from([4, 5, 6]), Vec3b::from([7, 8, 9])],
+		[
+			Vec3b::from([10, 11, 12]),
+			Vec3b::from([13, 14, 15]),
+			Vec3b::from([16, 17, 18]),
+		],
+	])?;
+	let img = mat.to_image_rgb()?;
+	assert_eq!((3, 2), img.dimensions());
+	assert_eq!([3, 2, 1], img.get_pixel(0, 0).0);
+	assert_eq!([18, 17, 16], img.get_pixel(2, 1).0);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	let img = roi.to_image_rgb()?;
+	assert_eq!((2, 2), img.dimensions());
+	assert_eq!([6, 5, 4], img.get_pixel(0, 0).0);
+
+	let mat_back = Mat::from_image_rgb(&img)?;
+	assert_eq!(Vec3b::opencv_type(), mat_back.typ());
+	assert_eq!(roi.to_vec_2d::<Vec3b>()?, mat_back.to_vec_2d::<Vec3b>()?);
+
+	let mat = Mat::new_rows_cols_with_default(2, 2, u8::opencv_type(), Scalar::all(0.))?;
+	assert_matches!(
+		mat.to_image_rgb(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_nalgebra() -> Result<()> {
+	#![cfg(feature = "nalgebra")]
+	let mat = Mat::from_slice_2d(&[[1f64, 2., 3.], [4., 5., 6.]])?;
+	let m = mat.to_nalgebra::<f64, 2, 3>()?;
+	assert_eq!(nalgebra::Matrix2x3::new(1., 2., 3., 4., 5., 6.), m);
+	assert_eq!(&[1., 4., 2., 5., 3., 6.], m.as_slice());
+
+	assert_matches!(
+		mat.to_nalgebra::<f64, 3, 2>(),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		mat.to_nalgebra::<f32, 2, 3>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_cast_slice() -> Result<()> {
+	#![cfg(feature = "bytemuck")]
+	let mat = Mat::from_slice_2d(&[
+		[Vec3f::from([1., 2., 3.]), Vec3f::from([4., 5., 6.])],
+		[Vec3f::from([7., 8., 9.]), Vec3f::from([10., 11., 12.])],
+	])?;
+	let data = mat.cast_slice::<[f32; 3]>()?;
+	assert_eq!(4, data.len());
+	assert_eq!([4., 5., 6.], data[1]);
+	assert_eq!(12, mat.cast_slice::<f32>()?.len());
+
+	assert_matches!(
+		mat.cast_slice::<[f32; 5]>(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 1, 2))?;
+	assert_matches!(
+		roi.cast_slice::<[f32; 3]>(),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_from_slice() -> Result<()> {
+	let src_u8 = [0u8, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+	let src_point = [
+		Point2d::new(10.1, 20.2),
+		Point2d::new(30.3, 40.4),
+		Point2d::new(50.5, 60.6),
+		Point2d::new(70.7, 80.8),
+	];
+
+	{
+		let mat = Mat::from_slice(&src_u8)?;
+		assert_eq!(10, mat.total());
+		assert_eq!(5u8, *mat.at(5)?);
+
+		let mat = Mat::from_slice(&src_point)?;
+		assert_eq!(4, mat.total());
+		assert_eq!(Point2d::new(30.3, 40.4), *mat.at(1)?);
+	}
+
+	{
+		let mat = Mat::new_rows_cols_with_data(2, 5, &src_u8)?;
+		assert_eq!(10, mat.total());
+		assert_eq!(2, mat.rows());
+		assert_eq!(5, mat.cols());
+		assert_eq!(6u8, *mat.at_2d(1, 1)?);
+
+		let mat_sz = Mat::new_size_with_data(Size::new(5, 2), &src_u8)?;
+		assert_eq!(mat.data_typed::<u8>()?, mat_sz.data_typed::<u8>()?);
+
+		let mat_nd = Mat::new_nd_with_data(&[2, 5], &src_u8)?;
+		assert_eq!(mat.rows(), mat_nd.rows());
+		assert_eq!(mat.cols(), mat_nd.cols());
+		assert_eq!(mat.data_typed::<u8>()?, mat_nd.data_typed::<u8>()?);
+
+		let mat_3d = Mat::new_nd_with_data(&[1, 2, 3], &src_u8[..6])?;
+		assert_eq!(3, mat_3d.dims());
+		assert_eq!(-1, mat_3d.rows());
+		assert_eq!(-1, mat_3d.cols());
+		let mat_size = mat_3d.mat_size();
+		assert_eq!(1, mat_size.get(0)?);
+		assert_eq!(2, mat_size.get(1)?);
+		assert_eq!(3, mat_size.get(2)?);
+	}
+
+	{
+		let mut src_u8 = src_u8;
+
+		let mut mat = Mat::new_rows_cols_with_data_mut(5, 2, &mut src_u8)?;
+		*mat.at_2d_mut::<u8>(3, 0)? = 222;
+		assert_eq!(222, src_u8[6]);
+
+		let mut mat_sz = Mat::new_size_with_data_mut(Size::new(2, 5), &mut src_u8)?;
+		*mat_sz.at_2d_mut::<u8>(3, 0)? = 211;
+		assert_eq!(211, src_u8[6]);
+
+		let mut mat = Mat::new_nd_with_data_mut(&[5, 2], &mut src_u8)?;
+		*mat.at_nd_mut::<u8>(&[3, 0])? = 111;
+		assert_eq!(111, src_u8[6]);
+	}
+
+	{
+		let mut src_u8 = src_u8;
+		let mut mat = Mat::from_slice_mut(&mut src_u8)?;
+		core::divide_def(100., &[2; 10], &mut mat)?;
+		assert_eq!([50; 10], src_u8);
+	}
+
+	{
+		let src: &[u8] = &[];
+		let mat_res = Mat::new_nd_with_data(&[], src);
+		assert_matches!(
+			mat_res,
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+
+	{
+		let src = [1u8; 20];
+		let src_mat = Mat::new_rows_cols_with_data(4, 5, &src)?;
+		let mut dst = [0u8; 10];
+		let mut dst_mat = Mat::new_rows_cols_with_data_mut(2, 5, &mut dst)?;
+		assert_eq!(Size::new(5, 2), dst_mat.size()?);
+		// this reallocates a `Mat` and disconnects in from the `dst` internally, so it's left untouched
+		imgproc::cvt_color_def(&src_mat, &mut dst_mat, imgproc::COLOR_GRAY2BGR)?;
+		assert_eq!(3, dst_mat.channels());
+		assert_eq!(Size::new(5, 4), dst_mat.size()?);
+		assert_eq!([0; 10], dst);
+	}
+
+	{
+		let mat_res = Mat::new_rows_cols_with_data(3, 3, &src_u8);
+		assert_matches!(
+			mat_res,
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+
+		let mat_res = Mat::new_size_with_data(Size::new(3, 3), &src_u8);
+		assert_matches!(
+			mat_res,
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+
+		let mat_res = Mat::new_nd_with_data(&[3, 3], &src_u8);
+		assert_matches!(
+			mat_res,
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+
+	{
+		let mat = Mat::new_rows_cols_with_data(2, 2, &src_point)?;
+		assert_eq!(4, mat.total());
+		assert_eq!(2, mat.rows());
+		assert_eq!(2, mat.cols());
+		assert_eq!(Point2d::new(50.5, 60.6), *mat.at_2d(1, 0)?);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn mat_from_slice_2d() -> Result<()> {
+	{
+		let src = [&[1, 2, 3, 4], &[5, 6, 7, 8], &[9, 10, 11, 12], &[13, 14, 15, 16u8]];
+		let mat = Mat::from_slice_2d(&src)?;
+		assert_eq!(4, mat.rows());
+		assert_eq!(4, mat.cols());
+		assert_eq!(16, mat.total());
+	}
+
+	{
+		// first row is shorter
+		let src = &[
+			[1, 2].as_slice(),
+			[5, 6, 7, 8].as_slice(),
+			[9, 10, 11, 12].as_slice(),
+			[13, 14, 15, 16u8].as_slice(),
+		];
+		let mat_res = Mat::from_slice_2d(src);
+		assert_matches!(
+			mat_res,
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn mat_try_from_slice_2d() -> Result<()> {
+	{
+		let src: &[&[f32]] = &[&[1., 2., 3.], &[4., 5., 6.]];
+		let mat: Mat = src.try_into()?;
+		assert_eq!(Size::new(3, 2), mat.size()?);
+		assert_eq!(f32::opencv_type(), mat.typ());
+		assert_eq!(6., *mat.at_2d::<f32>(1, 2)?);
+	}
+
+	{
+		let src: &[&[u8]] = &[&[1, 2, 3], &[4, 5]];
+		assert_matches!(
+			Mat::try_from(src),
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn mat_from_bytes_dyn() -> Result<()> {
+	let src = [1u16, 2, 3, 4, 5, 6];
+	let bytes = unsafe { std::slice::from_raw_parts(src.as_ptr().cast::<u8>(), mem::size_of_val(&src)) };
+	let mat = Mat::from_bytes_dyn(core::CV_16UC3, 2, 1, bytes)?;
+	assert_eq!(core::CV_16UC3, mat.typ());
+	assert_eq!(Size::new(1, 2), mat.size()?);
+	assert_eq!(bytes.as_ptr(), mat.data());
+	assert_eq!(Vec3w::from([4, 5, 6]), *mat.at_2d::<Vec3w>(1, 0)?);
+
+	assert_matches!(
+		Mat::from_bytes_dyn(core::CV_16UC3, 2, 2, bytes),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::from_bytes_dyn(core::CV_32SC4, 1, 1, bytes),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::from_bytes_dyn(-1, 1, 1, bytes),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_copy_rows_cols() -> Result<()> {
+	let src = [1i16, 2, 3, 4, 5, 6];
+	let mat = Mat::copy_rows_cols(3, 2, &src)?;
+	assert_eq!(Size::new(2, 3), mat.size()?);
+	assert_ne!(src.as_ptr(), mat.data().cast());
+	assert_eq!(&src, mat.data_typed::<i16>()?);
+
+	assert_matches!(
+		Mat::copy_rows_cols(2, 2, &src),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_from_slice_nd() -> Result<()> {
+	{
+		let src = [1u16, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+		let mat = Mat::from_slice_nd(&[2, 3, 2], &src)?;
+		assert_eq!(3, mat.dims());
+		assert_eq!([2, 3, 2], *mat.mat_size());
+		assert_ne!(src.as_ptr(), mat.data().cast());
+		assert_eq!(1, *mat.at_3d::<u16>(0, 0, 0)?);
+		assert_eq!(8, *mat.at_3d::<u16>(1, 0, 1)?);
+		assert_eq!(12, *mat.at_3d::<u16>(1, 2, 1)?);
+	}
+
+	{
+		let mat = Mat::from_slice_nd::<u8>(&[], &[])?;
+		assert!(mat.empty());
+	}
+
+	{
+		let mat_res = Mat::from_slice_nd(&[2, 2], &[1u8, 2, 3]);
+		assert_matches!(
+			mat_res,
+			Err(Error {
+				code: core::StsUnmatchedSizes,
+				..
+			})
+		);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn mat_custom_data_type() -> Result<()> {
+	#[repr(C)]
+	#[derive(Copy, Clone, Debug)]
+	struct A {
+		a: i16,
+		b: i16,
+	}
+
+	unsafe impl DataType for A {
+		fn opencv_depth() -> i32 {
+			core::CV_16S
+		}
+
+		fn opencv_channels() -> i32 {
+			2
+		}
+	}
+
+	let m = Mat::new_rows_cols_with_default(10, 10, Vec2s::opencv_type(), (-10, 20).into())?;
+	let data = m.data_typed::<A>()?;
+	assert!(!data.is_empty());
+	assert!(data.iter().all(|el| el.a == -10 && el.b == 20));
+	Ok(())
+}
+
+#[test]
+fn mat_reshape() -> Result<()> {
+	let mut slice = [1, 2, 3, 4, 5, 6];
+	let mut mat = Mat::from_slice_mut(&mut slice)?;
+
+	let mut mat2 = mat.reshape_mut(1, 2)?;
+	assert_eq!(mat2.cols(), 3);
+	assert_eq!(mat2.rows(), 2);
+	*mat2.at_2d_mut(1, 0)? = 10;
+
+	assert_eq!(&[1, 2, 3, 10, 5, 6], mat2.data_typed::<i32>()?);
+
+	Ok(())
+}
+
+#[test]
+fn mat_from_slice_channels() -> Result<()> {
+	let src = [1u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+	let mat = Mat::from_slice_channels(2, 2, 3, &src)?;
+	assert_eq!(core::CV_8UC3, mat.typ());
+	assert_eq!(Size::new(2, 2), mat.size()?);
+	assert_eq!(src.as_ptr(), mat.data());
+	assert_eq!(Vec3b::from([10, 11, 12]), *mat.at_2d::<Vec3b>(1, 1)?);
+
+	assert_matches!(
+		Mat::from_slice_channels(2, 2, 4, &src),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::from_slice_channels(2, 2, 0, &src),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	let vecs = [Vec3b::default(); 4];
+	assert_matches!(
+		Mat::from_slice_channels(2, 2, 1, &vecs),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_content_hash() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	let same = Mat::from_slice_2d(&[[1u8, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	assert_eq!(mat.content_hash()?, same.content_hash()?);
+
+	let reshaped = mat.reshape(1, 4)?;
+	assert_ne!(mat.content_hash()?, reshaped.content_hash()?);
+
+	let mut changed = same.clone();
+	*changed.at_2d_mut::<u8>(2, 3)? = 0;
+	assert_ne!(mat.content_hash()?, changed.content_hash()?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+	assert!(!roi.is_continuous());
+	let roi_copy = Mat::from_slice_2d(&[[6u8, 7], [10, 11]])?;
+	assert_eq!(roi_copy.content_hash()?, roi.content_hash()?);
+
+	assert_eq!(Mat::default().content_hash()?, Mat::default().content_hash()?);
+	Ok(())
+}
+
+#[test]
+fn mat_builder() -> Result<()> {
+	let mat = MatBuilder::new().rows(2).cols(3).typ(core::CV_16SC1).build()?;
+	assert_eq!(core::CV_16SC1, mat.typ());
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert!(mat.is_continuous());
+	assert_eq!(&[0i16; 6], mat.data_typed::<i16>()?);
+
+	let mat = MatBuilder::new()
+		.size(Size::new(2, 1))
+		.typ(core::CV_8UC3)
+		.fill(Scalar::new(1., 2., 3., 0.))
+		.build()?;
+	assert_eq!(&[Vec3b::from([1, 2, 3]); 2], mat.data_typed::<Vec3b>()?);
+
+	let mat = MatBuilder::new()
+		.rows(2)
+		.cols(2)
+		.typ(core::CV_8UC1)
+		.continuous(true)
+		.build()?;
+	assert!(mat.is_continuous());
+	let mat = MatBuilder::new()
+		.rows(2)
+		.cols(3)
+		.typ(core::CV_32FC1)
+		.fill(Scalar::all(1.5))
+		.continuous(false)
+		.build()?;
+	assert!(!mat.is_continuous());
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert_eq!(vec![vec![1.5; 3]; 2], mat.to_vec_2d::<f32>()?);
+
+	assert_matches!(
+		MatBuilder::new().rows(2).cols(3).build(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	assert_matches!(
+		MatBuilder::new().rows(2).typ(core::CV_8UC1).build(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	assert_matches!(
+		MatBuilder::new().rows(-1).cols(3).typ(core::CV_8UC1).build(),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_strides() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1i32, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	assert_eq!(vec![16, 4], mat.strides_bytes());
+	assert_eq!(vec![4, 1], mat.strides_elems());
+
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 2))?;
+	assert_eq!(vec![16, 4], roi.strides_bytes());
+	assert_eq!(vec![4, 1], roi.strides_elems());
+
+	let mat = Mat::new_nd_with_default(&[2, 3, 4], Vec3b::opencv_type(), 0.into())?;
+	assert_eq!(vec![36, 12, 3], mat.strides_bytes());
+	assert_eq!(vec![12, 4, 1], mat.strides_elems());
+
+	assert!(Mat::default().strides_bytes().is_empty());
+	Ok(())
+}
+
+#[test]
+fn mat_to_vec_flat() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u16, 2, 3], [4, 5, 6], [7, 8, 9]])?;
+	assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9], mat.to_vec_flat::<u16>()?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 3))?;
+	assert!(!roi.is_continuous());
+	assert_eq!(vec![2, 3, 5, 6, 8, 9], roi.to_vec_flat::<u16>()?);
+
+	let mat = Mat::new_nd_with_default(&[2, 2, 2], i32::opencv_type(), 7.into())?;
+	assert_eq!(vec![7; 8], mat.to_vec_flat::<i32>()?);
+
+	assert!(Mat::default().to_vec_flat::<u8>()?.is_empty());
+	assert_matches!(
+		roi.to_vec_flat::<i16>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_par_rows_mut() -> Result<()> {
+	#![cfg(feature = "rayon")]
+	use rayon::iter::{IndexedParallelIterator, ParallelIterator};
+
+	let mut mat = Mat::from_slice_2d(&[[1i32, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	assert_eq!(3, mat.par_rows_mut::<i32>()?.len());
+	mat.par_rows_mut::<i32>()?
+		.for_each(|row| row.iter_mut().for_each(|x| *x *= 2));
+	assert_eq!(&[2, 4, 6, 8, 10, 12, 14, 16, 18, 20, 22, 24], mat.data_typed::<i32>()?);
+
+	{
+		let mut roi = Mat::roi_mut(&mut mat, Rect::new(1, 1, 2, 2))?;
+		roi.par_rows_mut::<i32>()?.for_each(|row| row.fill(0));
+	}
+	assert_eq!(&[2, 4, 6, 8, 10, 0, 0, 16, 18, 0, 0, 24], mat.data_typed::<i32>()?);
+
+	assert_matches!(
+		mat.par_rows_mut::<u8>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_pad() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2], [3, 4]])?;
+	let padded = mat.pad(1, 0, 0, 2, core::BorderTypes::BORDER_CONSTANT, Scalar::all(9.))?;
+	assert_eq!(Size::new(4, 3), padded.size()?);
+	assert_eq!(&[9, 9, 9, 9, 1, 2, 9, 9, 3, 4, 9, 9], padded.data_typed::<u8>()?);
+
+	let padded = mat.pad(0, 1, 1, 0, core::BorderTypes::BORDER_REPLICATE, Scalar::all(9.))?;
+	assert_eq!(&[1, 1, 2, 3, 3, 4, 3, 3, 4], padded.data_typed::<u8>()?);
+
+	assert_matches!(
+		mat.pad(0, -1, 0, 0, core::BorderTypes::BORDER_CONSTANT, Scalar::default()),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_transpose_into() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let mut dst = Mat::default();
+	mat.transpose_into(&mut dst)?;
+	assert_eq!(Size::new(2, 3), dst.size()?);
+	assert_eq!(&[1, 4, 2, 5, 3, 6], dst.data_typed::<u8>()?);
+
+	let data = dst.data();
+	let mat = Mat::from_slice_2d(&[[7u8, 8, 9], [10, 11, 12]])?;
+	mat.transpose_into(&mut dst)?;
+	assert_eq!(data, dst.data());
+	assert_eq!(&[7, 10, 8, 11, 9, 12], dst.data_typed::<u8>()?);
+
+	let mat = Mat::new_nd_with_default(&[2, 2, 2], u8::opencv_type(), 0.into())?;
+	assert_matches!(
+		mat.transpose_into(&mut dst),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_normalize_minmax_f32() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[10u8, 20, 30], [50, 40, 60]])?;
+	let norm = mat.normalize_minmax_f32()?;
+	assert_eq!(Size::new(3, 2), norm.size()?);
+	assert_eq!(&[0., 0.2, 0.4, 0.8, 0.6, 1.], norm.data_typed()?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 1, 2))?;
+	assert_eq!(&[0., 1.], roi.normalize_minmax_f32()?.data_typed()?);
+
+	let flat = Mat::new_rows_cols_with_default(2, 2, i16::opencv_type(), (-5).into())?;
+	assert_eq!(&[0.; 4], flat.normalize_minmax_f32()?.data_typed()?);
+
+	assert!(Mat::default().normalize_minmax_f32()?.empty());
+	let multi = Mat::new_rows_cols_with_default(2, 2, Vec3b::opencv_type(), 1.into())?;
+	assert_matches!(
+		multi.normalize_minmax_f32(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_diag_typed() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1i32, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]])?;
+	assert_eq!(vec![1, 6, 11], mat.diag_typed::<i32>()?);
+	for x in mat.diag_typed_mut::<i32>()? {
+		*x = -*x;
+	}
+	assert_eq!(&[-1, 2, 3, 4, 5, -6, 7, 8, 9, 10, -11, 12], mat.data_typed::<i32>()?);
+
+	let roi = Mat::roi(&mat, Rect::new(2, 0, 2, 3))?;
+	assert_eq!(vec![3, 8], roi.diag_typed::<i32>()?);
+
+	assert_matches!(
+		mat.diag_typed::<u8>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_count_matching() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 200, 3], [150, 5, 255]])?;
+	assert_eq!(3, mat.count_matching(|&x: &u8| x > 100)?);
+	assert_eq!(0, mat.count_matching(|&x: &u8| x == 100)?);
+
+	let roi = Mat::roi(&mat, Rect::new(0, 0, 2, 2))?;
+	assert_eq!(2, roi.count_matching(|&x: &u8| x > 100)?);
+
+	assert_eq!(0, Mat::default().count_matching(|_: &u8| true)?);
+	assert_matches!(
+		mat.count_matching(|_: &i32| true),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_from_uninit_slice_mut() -> Result<()> {
+	let mut buf = [mem::MaybeUninit::<u16>::uninit(); 6];
+	{
+		let mut mat = unsafe { Mat::from_uninit_slice_mut(2, 3, &mut buf) }?;
+		assert_eq!(u16::opencv_type(), mat.typ());
+		assert_eq!(Size::new(3, 2), mat.size()?);
+		mat.set_to_def(&Scalar::all(7.))?;
+		assert_eq!(&[7; 6], mat.data_typed::<u16>()?);
+	}
+	assert_eq!(7, unsafe { buf[5].assume_init() });
+
+	assert_matches!(
+		unsafe { Mat::from_uninit_slice_mut(2, 2, &mut buf) },
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_reshape_to() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3, 4, 5, 6], [7, 8, 9, 10, 11, 12]])?;
+	let res = mat.reshape_to(3, Size::new(2, 2))?;
+	assert_eq!(Size::new(2, 2), res.size()?);
+	assert_eq!(core::CV_8UC3, res.typ());
+	assert_eq!(Vec3b::from([10, 11, 12]), *res.at_2d::<Vec3b>(1, 1)?);
+
+	let res = mat.reshape_to(0, Size::new(3, 4))?;
+	assert_eq!(Size::new(3, 4), res.size()?);
+	assert_eq!(core::CV_8UC1, res.typ());
+
+	assert_matches!(
+		mat.reshape_to(1, Size::new(5, 2)),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_split_channels() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[
+		[Vec3b::from([1, 2, 3]), Vec3b::from([4, 5, 6])],
+		[Vec3b::from([7, 8, 9]), Vec3b::from([10, 11, 12])],
+	])?;
+	let planes = mat.split_channels()?;
+	assert_eq!(3, planes.len());
+	assert_eq!(&[1, 4, 7, 10], planes[0].data_typed::<u8>()?);
+	assert_eq!(&[3, 6, 9, 12], planes[2].data_typed::<u8>()?);
+
+	let gray = Mat::from_slice_2d(&[[1u16, 2], [3, 4]])?;
+	let planes = gray.split_channels()?;
+	assert_eq!(1, planes.len());
+	assert_eq!(&[1, 2, 3, 4], planes[0].data_typed::<u16>()?);
+	Ok(())
+}
+
+#[test]
+fn mat_merge_channels() -> Result<()> {
+	let b = Mat::from_slice_2d(&[[1u8, 4], [7, 10]])?;
+	let g = Mat::from_slice_2d(&[[2u8, 5], [8, 11]])?;
+	let r = Mat::from_slice_2d(&[[3u8, 6], [9, 12]])?;
+	let mat = Mat::merge_channels(&[b.try_clone()?, g.try_clone()?, r.try_clone()?])?;
+	assert_eq!(core::CV_8UC3, mat.typ());
+	assert_eq!(Size::new(2, 2), mat.size()?);
+	assert_eq!(Vec3b::from([10, 11, 12]), *mat.at_2d::<Vec3b>(1, 1)?);
+	assert_eq!(mat.split_channels()?[1].data_typed::<u8>()?, g.data_typed::<u8>()?);
+
+	let many = vec![b.try_clone()?; 5];
+	assert_eq!(5, Mat::merge_channels(&many)?.channels());
+
+	let small = Mat::from_slice_2d(&[[1u8, 2]])?;
+	assert_matches!(
+		Mat::merge_channels(&[b.try_clone()?, small]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	let wide = Mat::from_slice_2d(&[[1u16, 4], [7, 10]])?;
+	assert_matches!(
+		Mat::merge_channels(&[b.try_clone()?, wide]),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	let empty: [Mat; 0] = [];
+	assert_matches!(
+		Mat::merge_channels(&empty),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_generate() -> Result<()> {
+	let mat = Mat::generate_8uc1(2, 3, |row, col| (row * 10 + col) as u8)?;
+	assert_eq!(core::CV_8UC1, mat.typ());
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert_eq!(&[0, 1, 2, 10, 11, 12], mat.data_typed::<u8>()?);
+
+	let mat = Mat::generate_32fc1(2, 2, |row, col| row as f32 - col as f32 / 2.)?;
+	assert_eq!(core::CV_32FC1, mat.typ());
+	assert_eq!(&[0., -0.5, 1., 0.5], mat.data_typed::<f32>()?);
+
+	assert!(Mat::generate_8uc1(0, 0, |_, _| 1)?.empty());
+	Ok(())
+}
+
+#[test]
+fn mat_is_same_buffer() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let roi = Mat::roi(&mat, Rect::new(1, 1, 2, 1))?;
+	assert!(mat.is_same_buffer(&roi));
+	assert!(roi.is_same_buffer(&mat));
+	let copy = mat.try_clone()?;
+	assert!(!mat.is_same_buffer(&copy));
+	assert!(!Mat::default().is_same_buffer(&Mat::default()));
+	Ok(())
+}
+
+#[test]
+fn mat_center_crop_aspect() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(100, 200, u8::opencv_type(), 0.into())?;
+	let crop = mat.center_crop_aspect(1, 1)?;
+	assert_eq!(Size::new(100, 100), crop.size()?);
+	assert_eq!(mat.ptr_2d(0, 50)?, crop.data());
+
+	let crop = mat.center_crop_aspect(4, 1)?;
+	assert_eq!(Size::new(200, 50), crop.size()?);
+	assert_eq!(mat.ptr_2d(25, 0)?, crop.data());
+
+	let tall = Mat::new_rows_cols_with_default(300, 90, u8::opencv_type(), 0.into())?;
+	let crop = tall.center_crop_aspect(16, 9)?;
+	assert_eq!(Size::new(90, 50), crop.size()?);
+	assert_eq!(tall.ptr_2d(125, 0)?, crop.data());
+
+	assert_matches!(
+		mat.center_crop_aspect(0, 1),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_histogram_u8() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[0u8, 5, 5], [255, 5, 0]])?;
+	let hist = mat.histogram_u8()?;
+	assert_eq!(2, hist[0]);
+	assert_eq!(3, hist[5]);
+	assert_eq!(1, hist[255]);
+	assert_eq!(6, hist.iter().sum::<u32>());
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	let hist = roi.histogram_u8()?;
+	assert_eq!(0, hist[255]);
+	assert_eq!(3, hist[5]);
+
+	let wrong = Mat::from_slice(&[1i32, 2])?;
+	assert_matches!(
+		wrong.histogram_u8(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_resize_into() -> Result<()> {
+	let src = Mat::new_rows_cols_with_default(4, 6, u8::opencv_type(), 5.into())?;
+	let mut dst = Mat::new_rows_cols_with_default(2, 3, u8::opencv_type(), 0.into())?;
+	let data = dst.data();
+	src.resize_into(&mut dst, imgproc::InterpolationFlags::INTER_NEAREST)?;
+	assert_eq!(Size::new(3, 2), dst.size()?);
+	assert_eq!(data, dst.data());
+	assert_eq!(&[5; 6], dst.data_typed::<u8>()?);
+
+	assert_matches!(
+		src.resize_into(&mut Mat::default(), imgproc::InterpolationFlags::INTER_LINEAR),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_element_type() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(1, 1, Vec3b::opencv_type(), 0.into())?;
+	let elem_type = mat.element_type();
+	assert_eq!(
+		MatElementType {
+			depth: MatDepth::U8,
+			channels: 3
+		},
+		elem_type
+	);
+	assert_eq!(core::CV_8UC3, elem_type.opencv_type());
+
+	let mat = Mat::from_slice(&[1., 2.])?;
+	assert_eq!(MatDepth::F64, mat.element_type().depth);
+	assert_eq!(1, mat.element_type().channels);
+
+	assert_eq!(core::CV_16S, MatDepth::from_opencv_depth(core::CV_16S)?.opencv_depth());
+	assert_matches!(
+		MatElementType::from_opencv_type(-1),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+
+	let typ = core::CV_MAKETYPE(core::CV_8U, 300);
+	let elem_type = MatElementType::from_opencv_type(typ)?;
+	assert_eq!(300, elem_type.channels);
+	assert_eq!(typ, elem_type.opencv_type());
+	let mat = unsafe { Mat::new_rows_cols(1, 1, typ) }?;
+	assert_eq!(elem_type, mat.element_type());
+	Ok(())
+}
+
+#[test]
+fn mat_depth_enum() -> Result<()> {
+	let mat = Mat::new_rows_cols_with_default(1, 1, Vec3w::opencv_type(), 0.into())?;
+	assert_eq!(MatDepth::U16, mat.depth_enum());
+	let elem_type = MatElementType {
+		depth: mat.depth_enum(),
+		channels: mat.channels(),
+	};
+	assert_eq!(mat.typ(), elem_type.opencv_type());
+	assert_eq!(MatDepth::F32, Mat::from_slice(&[1f32])?.depth_enum());
+	assert_eq!(core::CV_32S, MatDepth::S32.opencv_depth());
+	assert_eq!(42, MatDepth::Other(42).opencv_depth());
+	Ok(())
+}
+
+#[test]
+fn mat_to_json_summary() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	assert_eq!(
+		r#"{"type":"CV_8UC1","rows":2,"cols":3,"channels":1,"is_continuous":true,"data":[[1,2,3],[4,5,6]]}"#,
+		mat.to_json_summary()
+	);
+
+	let mat = Mat::from_slice_2d(&[[1.5f32, f32::NAN], [-2., f32::INFINITY]])?;
+	let roi = mat.col(1)?;
+	assert_eq!(
+		r#"{"type":"CV_32FC1","rows":2,"cols":1,"channels":1,"is_continuous":false,"data":[[null],[null]]}"#,
+		roi.to_json_summary()
+	);
+	assert_eq!(
+		r#"{"type":"CV_32FC1","rows":2,"cols":2,"channels":1,"is_continuous":true,"data":[[1.5,null],[-2,null]]}"#,
+		mat.to_json_summary()
+	);
+
+	let mat = Mat::new_rows_cols_with_default(10, 4, Vec3w::opencv_type(), 0.into())?;
+	assert_eq!(
+		r#"{"type":"CV_16UC3","rows":10,"cols":4,"channels":3,"is_continuous":true}"#,
+		mat.to_json_summary()
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_threshold_binary() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[10u8, 100], [128, 200]])?;
+	let mask = mat.threshold_binary(100., 255.)?;
+	assert_eq!(u8::opencv_type(), mask.typ());
+	assert_eq!(&[0, 0, 255, 255], mask.data_typed::<u8>()?);
+
+	let mat = Mat::new_rows_cols_with_default(2, 2, Vec3b::opencv_type(), 0.into())?;
+	assert_matches!(
+		mat.threshold_binary(100., 255.),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_pool() -> Result<()> {
+	let pool = MatPool::new();
+	let data = {
+		let mut mat = pool.get(2, 3, u8::opencv_type())?;
+		assert_eq!(Size::new(3, 2), mat.size()?);
+		assert_eq!(&[0; 6], mat.data_typed::<u8>()?);
+		mat.set_to_def(&Scalar::all(7.))?;
+		mat.data()
+	};
+	assert_eq!(1, pool.available());
+
+	let mat = pool.get(2, 3, f32::opencv_type())?;
+	assert_eq!(f32::opencv_type(), mat.typ());
+	assert_eq!(1, pool.available());
+	drop(mat);
+	assert_eq!(2, pool.available());
+
+	let mat = pool.get(2, 3, u8::opencv_type())?;
+	assert_eq!(data, mat.data());
+	assert_eq!(&[7; 6], mat.data_typed::<u8>()?);
+	let mat = mat.into_inner();
+	assert_eq!(1, pool.available());
+	drop(mat);
+	assert_eq!(1, pool.available());
+
+	pool.clear();
+	assert_eq!(0, pool.available());
+	Ok(())
+}
+
+#[test]
+fn mat_mean_f64() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 7]])?;
+	assert_eq!(Scalar::new(22. / 6., 0., 0., 0.), mat.mean_f64()?);
+	assert_eq!(Scalar::new(2.5, 0., 0., 0.), mat.col(1)?.mean_f64()?);
+
+	let mat = Mat::from_slice_2d(&[[Vec3f::from([1., 10., 100.]), Vec3f::from([3., 30., 300.])]])?;
+	assert_eq!(Scalar::new(2., 20., 200., 0.), mat.mean_f64()?);
+	assert_eq!(core::mean_def(&mat)?, mat.mean_f64()?);
+
+	assert_matches!(
+		Mat::default().mean_f64(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	let mat = Mat::new_rows_cols_with_default(1, 1, core::CV_MAKETYPE(core::CV_8U, 5), 0.into())?;
+	assert_matches!(
+		mat.mean_f64(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_from_rows() -> Result<()> {
+	let row1 = [1u16, 2, 3];
+	let row2 = [4u16, 5, 6];
+	let mat = Mat::from_rows(&[&row1[..], &row2[..]])?;
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert_eq!(&[1, 2, 3, 4, 5, 6], mat.data_typed::<u16>()?);
+
+	assert_matches!(
+		Mat::from_rows(&[&row1[..], &row2[..2]]),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_collect_rows() -> Result<()> {
+	let mat = Mat::collect_rows((0..3).map(|i| vec![i as f32; 4]), 4)?;
+	assert_eq!(Size::new(4, 3), mat.size()?);
+	assert_eq!(f32::opencv_type(), mat.typ());
+	assert_eq!(
+		[0., 1., 2.],
+		[*mat.at_2d::<f32>(0, 3)?, *mat.at_2d::<f32>(1, 0)?, *mat.at_2d::<f32>(2, 2)?]
+	);
+
+	let mat = Mat::collect_rows(std::iter::empty::<Vec<u8>>(), 5)?;
+	assert_eq!(Size::new(5, 0), mat.size()?);
+	assert_eq!(u8::opencv_type(), mat.typ());
+
+	assert_matches!(
+		Mat::collect_rows(vec![vec![1u8, 2], vec![3]].into_iter(), 2),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::collect_rows(std::iter::empty::<Vec<u8>>(), -1),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_to_array_2d() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]])?;
+	assert_eq!([[1., 2., 3.], [4., 5., 6.], [7., 8., 9.]], mat.to_array_2d::<f64, 3, 3>()?);
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	assert_eq!([[2., 3.], [5., 6.]], roi.to_array_2d::<f64, 2, 2>()?);
+
+	assert_matches!(
+		mat.to_array_2d::<f64, 2, 3>(),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		mat.to_array_2d::<f32, 3, 3>(),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_overlay_at() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(4, 4, u8::opencv_type(), 0.into())?;
+	let src = Mat::new_rows_cols_with_default(2, 2, u8::opencv_type(), 100.into())?;
+	mat.overlay_at(&src, Point::new(1, 1), 1.)?;
+	mat.overlay_at(&src, Point::new(3, 3), 0.5)?;
+	mat.overlay_at(&src, Point::new(-1, -1), 0.5)?;
+	mat.overlay_at(&src, Point::new(10, -10), 0.5)?;
+	assert_eq!(
+		[[50, 0, 0, 0], [0, 100, 100, 0], [0, 100, 100, 0], [0, 0, 0, 50]],
+		mat.to_array_2d::<u8, 4, 4>()?
+	);
+
+	assert_matches!(
+		mat.overlay_at(&src, Point::new(0, 0), 1.5),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	let src = Mat::new_rows_cols_with_default(2, 2, f32::opencv_type(), 100.into())?;
+	assert_matches!(
+		mat.overlay_at(&src, Point::new(0, 0), 0.5),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_convert_to_typed() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2], [3, 255]])?;
+	let out = mat.convert_to_typed::<f32>(0.5, 1.)?;
+	assert_eq!(&[1.5, 2., 2.5, 128.5], out.data_typed()?);
+
+	let mat = Mat::new_rows_cols_with_default(1, 2, Vec3b::opencv_type(), Scalar::new(1., 2., 3., 0.))?;
+	let out = mat.convert_to_typed::<Vec3f>(2., 0.)?;
+	assert_eq!(&[Vec3f::from([2., 4., 6.]); 2], out.data_typed()?);
+	assert_matches!(
+		mat.convert_to_typed::<f32>(1., 0.),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_sample_border() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	assert_eq!(5, mat.sample_border::<u8>(1, 1, core::BorderTypes::BORDER_CONSTANT)?);
+	assert_eq!(1, mat.sample_border::<u8>(-2, -1, core::BorderTypes::BORDER_REPLICATE)?);
+	assert_eq!(5, mat.sample_border::<u8>(1, 3, core::BorderTypes::BORDER_REFLECT_101)?);
+	assert_eq!(3, mat.sample_border::<u8>(2, 5, core::BorderTypes::BORDER_WRAP)?);
+	assert_eq!(4, mat.sample_border::<u8>(2, -1, core::BorderTypes::BORDER_REFLECT)?);
+
+	assert_matches!(
+		mat.sample_border::<u8>(-1, 0, core::BorderTypes::BORDER_CONSTANT),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		mat.sample_border::<u16>(0, 0, core::BorderTypes::BORDER_REPLICATE),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_nonzero_points() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[0u8, 255, 0], [1, 0, 0], [0, 0, 7]])?;
+	let expected = vec![Point::new(1, 0), Point::new(0, 1), Point::new(2, 2)];
+	assert_eq!(expected, mat.nonzero_points()?);
+	let mut cv_points = Vector::<Point>::new();
+	core::find_non_zero(&mat, &mut cv_points)?;
+	assert_eq!(expected, cv_points.to_vec());
+
+	let mat = Mat::from_slice_2d(&[[0f32, -0.5], [0., 0.]])?;
+	assert_eq!(
+		vec![Point::new(0, 0)],
+		Mat::roi(&mat, Rect::new(1, 0, 1, 2))?.nonzero_points()?
+	);
+	assert!(Mat::default().nonzero_points()?.is_empty());
+
+	let mat = Mat::new_rows_cols_with_default(1, 1, Vec3b::opencv_type(), 0.into())?;
+	assert_matches!(
+		mat.nonzero_points(),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_rebind_data() -> Result<()> {
+	let mut frame1 = [1u8, 2, 3, 4, 5, 6];
+	let mut frame2 = [6u8, 5, 4, 3, 2, 1];
+	let mut mat = Mat::new_rows_cols_with_data_mut(2, 3, &mut frame1)?;
+	assert_eq!(&[1, 2, 3, 4, 5, 6], mat.data_typed::<u8>()?);
+	Mat::rebind_data(&mut mat, &mut frame2)?;
+	assert_eq!(Size::new(3, 2), mat.size()?);
+	assert_eq!(&[6, 5, 4, 3, 2, 1], mat.data_typed::<u8>()?);
+	*mat.at_2d_mut::<u8>(0, 0)? = 10;
+	drop(mat);
+	assert_eq!([10, 5, 4, 3, 2, 1], frame2);
+
+	let mut frame3 = [0u8; 6];
+	let mut short = [0u8; 5];
+	let mut wide = [0u16; 6];
+	let mut mat = Mat::new_rows_cols_with_data_mut(2, 3, &mut frame3)?;
+	assert_matches!(
+		Mat::rebind_data(&mut mat, &mut short),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::rebind_data(&mut mat, &mut wide),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+
+	let mut row = [0u8; 3];
+	let mut owned = Mat::new_rows_cols_with_default(2, 3, u8::opencv_type(), 0.into())?;
+	let mut view = owned.row_mut(0)?;
+	assert_matches!(
+		Mat::rebind_data(&mut view, &mut row),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+
+	let mut row = [0u8; 3];
+	let mut frame4 = [1u8, 2, 3, 4, 5, 6];
+	let mut mat = Mat::new_rows_cols_with_data_mut(2, 3, &mut frame4)?;
+	let mut roi = mat.row_mut(1)?;
+	assert!(roi.is_submatrix());
+	assert_matches!(
+		Mat::rebind_data(&mut roi, &mut row),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	assert_eq!(&[4, 5, 6], roi.data_typed::<u8>()?);
+	Ok(())
+}
+
+#[test]
+fn mat_continuous_cow() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let cow = mat.continuous_cow()?;
+	assert!(cow.is_borrowed());
+	assert!(cow.is_same_buffer(&mat));
+	assert_eq!(&[1, 2, 3, 4, 5, 6], cow.data_typed::<u8>()?);
+
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	let cow = roi.continuous_cow()?;
+	assert_matches!(cow, MatCow::Owned(_));
+	assert!(cow.is_continuous());
+	assert!(!cow.is_same_buffer(&mat));
+	assert_eq!(&[2, 3, 5, 6], cow.data_typed::<u8>()?);
+	let owned = cow.into_owned()?;
+	assert_eq!(Size::new(2, 2), owned.size()?);
+	Ok(())
+}
+
+#[test]
+fn mat_half_f16() -> Result<()> {
+	#![cfg(feature = "half")]
+	use half::f16;
+
+	assert_eq!(core::CV_16F, f16::opencv_type());
+	assert_eq!(1, f16::opencv_channels());
+	let data = [f16::from_f32(0.5), f16::from_f32(-1.25), f16::from_f32(1024.)];
+	let mat = Mat::from_slice(&data)?;
+	assert_eq!(core::CV_16FC1, mat.typ());
+	assert_eq!(&data, mat.data_typed::<f16>()?);
+	assert_eq!(data[1], *mat.at::<f16>(1)?);
+	assert_eq!(data.to_vec(), mat.iter::<f16>()?.values().collect::<Vec<_>>());
+
+	let mut converted = Mat::default();
+	mat.convert_to(&mut converted, f32::opencv_type(), 1., 0.)?;
+	assert_eq!(&[0.5, -1.25, 1024.], converted.data_typed::<f32>()?);
+	Ok(())
+}
+
+#[test]
+fn mat_set_scalar() -> Result<()> {
+	let mut mat = Mat::new_rows_cols_with_default(2, 2, Vec3b::opencv_type(), 0.into())?;
+	mat.set_scalar(Scalar::new(1., 2., 3., 0.), None::<&Mat>)?;
+	assert_eq!(&[Vec3b::from([1, 2, 3]); 4], mat.data_typed::<Vec3b>()?);
+
+	let mask = Mat::from_slice_2d(&[[0u8, 1], [255, 0]])?;
+	mat.set_scalar(Scalar::all(9.), Some(&mask))?;
+	assert_eq!(
+		&[
+			Vec3b::from([1, 2, 3]),
+			Vec3b::from([9, 9, 9]),
+			Vec3b::from([9, 9, 9]),
+			Vec3b::from([1, 2, 3])
+		],
+		mat.data_typed::<Vec3b>()?
+	);
+
+	let mask = Mat::from_slice_2d(&[[1u8, 1]])?;
+	assert_matches!(
+		mat.set_scalar(Scalar::all(0.), Some(&mask)),
+		Err(Error {
+			code: core::StsUnmatchedSizes,
+			..
+		})
+	);
+	let mask = Mat::from_slice_2d(&[[1f32, 1.], [0., 0.]])?;
+	assert_matches!(
+		mat.set_scalar(Scalar::all(0.), Some(&mask)),
+		Err(Error {
+			code: core::StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_umat_round_trip() -> Result<()> {
+	let mut mat = Mat::from_slice_2d(&[[1u8, 2, 3], [4, 5, 6]])?;
+	let umat = mat.to_umat(core::UMatUsageFlags::USAGE_DEFAULT)?;
+	assert_eq!(Size::new(3, 2), umat.size()?);
+	assert_eq!(mat.typ(), umat.typ());
+	mat.set_scalar(Scalar::all(0.), None::<&Mat>)?;
+	drop(mat);
+
+	let back = umat.to_mat()?;
+	assert_eq!(vec![vec![1, 2, 3], vec![4, 5, 6]], back.to_vec_2d::<u8>()?);
+	Ok(())
+}
+
+#[test]
+fn mat_flood_fill_region() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[10u8, 10, 50, 10], [10, 12, 50, 10], [50, 50, 50, 10]])?;
+	let region = mat.flood_fill_region(Point::new(0, 0), Scalar::all(2.), Scalar::all(2.))?;
+	assert_eq!(Size::new(4, 3), region.size()?);
+	assert_eq!(u8::opencv_type(), region.typ());
+	assert_eq!(
+		vec![vec![255, 255, 0, 0], vec![255, 255, 0, 0], vec![0, 0, 0, 0]],
+		region.to_vec_2d::<u8>()?
+	);
+	assert_eq!(
+		vec![vec![10, 10, 50, 10], vec![10, 12, 50, 10], vec![50, 50, 50, 10]],
+		mat.to_vec_2d::<u8>()?
+	);
+
+	assert_matches!(
+		mat.flood_fill_region(Point::new(4, 0), Scalar::all(0.), Scalar::all(0.)),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	Ok(())
+}
+
+#[test]
+fn mat_quantile() -> Result<()> {
+	let mat = Mat::from_slice_2d(&[[9f32, 1., 5.], [3., 7., 2.]])?;
+	assert_eq!(1., mat.quantile::<f32>(0.)?);
+	assert_eq!(9., mat.quantile::<f32>(1.)?);
+	assert_eq!(5., mat.quantile::<f32>(0.5)?);
+	assert_eq!(2., mat.quantile::<f32>(0.2)?);
+	let roi = Mat::roi(&mat, Rect::new(1, 0, 2, 2))?;
+	assert_eq!(7., roi.quantile::<f32>(1.)?);
+
+	assert_matches!(
+		mat.quantile::<f32>(1.5),
+		Err(Error {
+			code: core::StsOutOfRange,
+			..
+		})
+	);
+	assert_matches!(
+		Mat::default().quantile::<u8>(0.5),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
+	let mat = Mat::new_rows_cols_with_default(1, 1, Vec3b::opencv_type(), 0.into())?;
+	assert_matches!(
+		mat.quantile::<u8>(0.5),
+		Err(Error {
+			code: core::StsBadArg,
+			..
+		})
+	);
 	Ok(())
 }
@@ -0,0 +1,49 @@
+//! Lives in a separate test binary because it installs a global allocator to observe when the `Vec` passed to
+//! `Mat::from_vec()` is released
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use opencv::core::{Mat, Vector};
+use opencv::prelude::*;
+use opencv::Result;
+
+static WATCHED_PTR: AtomicUsize = AtomicUsize::new(0);
+static WATCHED_FREED: AtomicBool = AtomicBool::new(false);
+
+/// System allocator that records the deallocation of the allocation at `WATCHED_PTR`
+struct WatchingAllocator;
+
+unsafe impl GlobalAlloc for WatchingAllocator {
+	unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+		System.alloc(layout)
+	}
+
+	unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+		if ptr as usize == WATCHED_PTR.load(Ordering::SeqCst) {
+			WATCHED_FREED.store(true, Ordering::SeqCst);
+		}
+		System.dealloc(ptr, layout)
+	}
+}
+
+#[global_allocator]
+static ALLOCATOR: WatchingAllocator = WatchingAllocator;
+
+#[test]
+fn mat_from_vec_drops_vec() -> Result<()> {
+	let data = (0..12).collect::<Vec<u16>>();
+	WATCHED_PTR.store(data.as_ptr() as usize, Ordering::SeqCst);
+	let mat = Mat::from_vec(data, 3, 4)?;
+	assert_eq!(5, *mat.at_2d::<u16>(1, 1)?);
+
+	// the Vector holds its own reference to the data, the Vec must outlive the original Mat
+	let mut mats = Vector::<Mat>::new();
+	mats.push(mat);
+	assert!(!WATCHED_FREED.load(Ordering::SeqCst));
+	assert_eq!(11, *mats.get(0)?.at_2d::<u16>(2, 3)?);
+
+	drop(mats);
+	assert!(WATCHED_FREED.load(Ordering::SeqCst));
+	Ok(())
+}
@@ -1,6 +1,6 @@
 use matches::assert_matches;
 
-use opencv::core::{add_mat_mat, ElemMul, Scalar, StsBadArg};
+use opencv::core::{add_mat_mat, ElemMul, Scalar, StsBadArg, StsUnmatchedFormats};
 use opencv::prelude::*;
 use opencv::{Error, Result};
 
@@ -101,3 +101,22 @@ fn mat_ops() -> Result<()> {
 
 	Ok(())
 }
+
+#[test]
+fn mat_expr_to_mat_typed() -> Result<()> {
+	#![allow(non_upper_case_globals)]
+	let lhs = Mat::new_rows_cols_with_default(2, 2, f32::opencv_type(), 1.5.into())?;
+	let rhs = Mat::new_rows_cols_with_default(2, 2, f32::opencv_type(), 2.into())?;
+	let expr = (&lhs + &rhs).into_result()?;
+	let res = expr.to_mat_typed::<f32>()?;
+	assert_eq!(&[3.5, 3.5, 3.5, 3.5], res.data_typed()?);
+
+	assert_matches!(
+		expr.to_mat_typed::<u8>(),
+		Err(Error {
+			code: StsUnmatchedFormats,
+			..
+		})
+	);
+	Ok(())
+}
@@ -0,0 +1,10 @@
+use opencv::core::{Scalar, Scalar_};
+
+#[test]
+fn scalar_colors() {
+	assert_eq!(Scalar::new(1., 2., 3., 0.), Scalar::bgr(1., 2., 3.));
+	assert_eq!(Scalar::new(3., 2., 1., 0.), Scalar::rgb(1., 2., 3.));
+	assert_eq!(Scalar::bgr(10., 20., 30.), Scalar::rgb(30., 20., 10.));
+	assert_eq!(Scalar::new(5., 0., 0., 0.), Scalar::gray(5.));
+	assert_eq!(Scalar_::<i32>::new(0, 0, 255, 0), Scalar_::rgb(255, 0, 0));
+}